@@ -14,6 +14,11 @@ fn main() -> Result<()> {
         println!("\tPCI subsys vendor: {:#x}", d.pci_subsys_vendor_id());
         println!("\tDevice type: {:?}", d.device_type());
 
+        let info = d.info();
+        println!("\tDriver version: {}", info.driver_version);
+        println!("\tAdapter flags: {:?}", info.adapter_flags);
+        println!("\tEU count: {}", info.eu_count());
+
         println!(
             "\tEndurance gaming: {:?}",
             d.feature_endurance_gaming(DriverSettingScope::CurrentProcess)
@@ -26,6 +31,22 @@ fn main() -> Result<()> {
             "\tFlip mode: {:?}",
             d.feature_flip_mode(DriverSettingScope::CurrentProcess)
         );
+
+        for output in d.display_outputs()? {
+            if !output.is_attached() {
+                continue;
+            }
+
+            let timing = output.timing();
+            println!(
+                "\tDisplay output: {:?} {}x{} @ {:.2} Hz (active: {})",
+                output.output_type(),
+                timing.h_active,
+                timing.v_active,
+                timing.refresh_rate,
+                output.is_active()
+            );
+        }
     }
 
     Ok(())
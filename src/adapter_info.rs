@@ -0,0 +1,135 @@
+use crate::{
+    driver_version::DriverVersion,
+    ffi::{ctl_adapter_properties_flag_t, ctl_device_type_t, ctl_supported_functions_flag_t},
+};
+
+#[doc(alias = "ctl_device_type_t")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DeviceType {
+    Graphics,
+    System,
+    /// A device type not known to these bindings.
+    Unknown(i32),
+}
+
+impl From<ctl_device_type_t> for DeviceType {
+    fn from(device_type: ctl_device_type_t) -> Self {
+        match device_type {
+            ctl_device_type_t::CTL_DEVICE_TYPE_GRAPHICS => Self::Graphics,
+            ctl_device_type_t::CTL_DEVICE_TYPE_SYSTEM => Self::System,
+            ctl_device_type_t(x) => Self::Unknown(x),
+        }
+    }
+}
+
+bitflags::bitflags! {
+    #[doc(alias = "ctl_supported_functions_flags_t")]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct SupportedFunctions: u32 {
+        const DISPLAY = ctl_supported_functions_flag_t::CTL_SUPPORTED_FUNCTIONS_FLAG_DISPLAY.0 as u32;
+        const THREE_D = ctl_supported_functions_flag_t::CTL_SUPPORTED_FUNCTIONS_FLAG_3D.0 as u32;
+        const MEDIA = ctl_supported_functions_flag_t::CTL_SUPPORTED_FUNCTIONS_FLAG_MEDIA.0 as u32;
+    }
+}
+
+bitflags::bitflags! {
+    #[doc(alias = "ctl_adapter_properties_flags_t")]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct AdapterPropertiesFlags: u32 {
+        /// Integrated graphics adapter.
+        const INTEGRATED = ctl_adapter_properties_flag_t::CTL_ADAPTER_PROPERTIES_FLAG_INTEGRATED.0 as u32;
+        /// Primary (lead) adapter in a Linked Display Adapter (LDA) chain.
+        const LDA_PRIMARY = ctl_adapter_properties_flag_t::CTL_ADAPTER_PROPERTIES_FLAG_LDA_PRIMARY.0 as u32;
+        /// Secondary (linked) adapter in a Linked Display Adapter (LDA) chain.
+        const LDA_SECONDARY = ctl_adapter_properties_flag_t::CTL_ADAPTER_PROPERTIES_FLAG_LDA_SECONDARY.0 as u32;
+    }
+}
+
+/// Owned snapshot of the properties of a [`DeviceAdapter`][crate::device_adapter::DeviceAdapter],
+/// retrieved through [`DeviceAdapter::info()`][crate::device_adapter::DeviceAdapter::info].
+#[doc(alias = "ctl_device_adapter_properties_t")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AdapterInfo {
+    pub name: String,
+    /// Unique device identifier determined by the operating system, see
+    /// [`DeviceAdapter::device_id()`][crate::device_adapter::DeviceAdapter::device_id].
+    pub device_id: Vec<u8>,
+    pub device_type: DeviceType,
+    pub supported_functions: SupportedFunctions,
+    pub adapter_flags: AdapterPropertiesFlags,
+    pub driver_version: DriverVersion,
+    pub pci_vendor_id: u32,
+    pub pci_device_id: u32,
+    pub pci_subsys_vendor_id: u16,
+    pub pci_subsys_id: u16,
+    /// PCI Revision ID.
+    pub rev_id: u32,
+    /// PCI Bus ID, PCI Device ID and PCI Device Function in that order.
+    pub bus_device_function: (u8, u8, u8),
+    pub num_eus_per_sub_slice: u32,
+    pub num_sub_slices_per_slice: u32,
+    pub num_slices: u32,
+    /// Number of Xe cores, or `0` if not reported by the driver.
+    pub num_xe_cores: u32,
+    /// Average graphics clock in MHz that may be seen in a typical gaming workload,
+    /// or `0` if not reported by the driver.
+    #[doc(alias = "Frequency")]
+    pub frequency: u32,
+}
+
+impl AdapterInfo {
+    /// Total number of execution units on the adapter, saturating at [`u64::MAX`] for bogus
+    /// driver-reported counts.
+    pub fn eu_count(&self) -> u64 {
+        u64::from(self.num_eus_per_sub_slice)
+            .saturating_mul(self.num_sub_slices_per_slice.into())
+            .saturating_mul(self.num_slices.into())
+    }
+
+    pub fn is_integrated(&self) -> bool {
+        self.adapter_flags
+            .contains(AdapterPropertiesFlags::INTEGRATED)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(eus: u32, sub_slices: u32, slices: u32) -> AdapterInfo {
+        AdapterInfo {
+            name: String::new(),
+            device_id: vec![],
+            device_type: DeviceType::Graphics,
+            supported_functions: SupportedFunctions::empty(),
+            adapter_flags: AdapterPropertiesFlags::empty(),
+            driver_version: DriverVersion::default(),
+            pci_vendor_id: 0,
+            pci_device_id: 0,
+            pci_subsys_vendor_id: 0,
+            pci_subsys_id: 0,
+            rev_id: 0,
+            bus_device_function: (0, 0, 0),
+            num_eus_per_sub_slice: eus,
+            num_sub_slices_per_slice: sub_slices,
+            num_slices: slices,
+            num_xe_cores: 0,
+            frequency: 0,
+        }
+    }
+
+    #[test]
+    fn eu_count() {
+        assert_eq!(info(16, 8, 4).eu_count(), 512);
+        assert_eq!(info(16, 8, 0).eu_count(), 0);
+        assert_eq!(
+            info(u32::MAX, u32::MAX, 1).eu_count(),
+            u64::from(u32::MAX) * u64::from(u32::MAX)
+        );
+        assert_eq!(info(u32::MAX, u32::MAX, 2).eu_count(), u64::MAX);
+    }
+}
@@ -0,0 +1,605 @@
+use crate::{
+    display::pixel_pipeline::{Matrix3x3, OneDLut},
+    ffi::{
+        ctl_pixtx_color_model_t, ctl_pixtx_color_primaries_t, ctl_pixtx_color_space_t,
+        ctl_pixtx_gamma_encoding_type_t,
+    },
+};
+
+#[doc(alias = "ctl_pixtx_gamma_encoding_type_t")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GammaEncoding {
+    Srgb,
+    /// Also used for BT.2020.
+    Rec709,
+    /// Perceptual quantizer (PQ).
+    St2084,
+    Hlg,
+    Linear,
+    /// A gamma encoding not known to these bindings.
+    Unknown(i32),
+}
+
+impl From<ctl_pixtx_gamma_encoding_type_t> for GammaEncoding {
+    fn from(encoding: ctl_pixtx_gamma_encoding_type_t) -> Self {
+        match encoding {
+            ctl_pixtx_gamma_encoding_type_t::CTL_PIXTX_GAMMA_ENCODING_TYPE_SRGB => Self::Srgb,
+            ctl_pixtx_gamma_encoding_type_t::CTL_PIXTX_GAMMA_ENCODING_TYPE_REC709 => Self::Rec709,
+            ctl_pixtx_gamma_encoding_type_t::CTL_PIXTX_GAMMA_ENCODING_TYPE_ST2084 => Self::St2084,
+            ctl_pixtx_gamma_encoding_type_t::CTL_PIXTX_GAMMA_ENCODING_TYPE_HLG => Self::Hlg,
+            ctl_pixtx_gamma_encoding_type_t::CTL_PIXTX_GAMMA_ENCODING_TYPE_LINEAR => Self::Linear,
+            ctl_pixtx_gamma_encoding_type_t(x) => Self::Unknown(x),
+        }
+    }
+}
+
+impl From<GammaEncoding> for ctl_pixtx_gamma_encoding_type_t {
+    fn from(encoding: GammaEncoding) -> Self {
+        match encoding {
+            GammaEncoding::Srgb => Self::CTL_PIXTX_GAMMA_ENCODING_TYPE_SRGB,
+            GammaEncoding::Rec709 => Self::CTL_PIXTX_GAMMA_ENCODING_TYPE_REC709,
+            GammaEncoding::St2084 => Self::CTL_PIXTX_GAMMA_ENCODING_TYPE_ST2084,
+            GammaEncoding::Hlg => Self::CTL_PIXTX_GAMMA_ENCODING_TYPE_HLG,
+            GammaEncoding::Linear => Self::CTL_PIXTX_GAMMA_ENCODING_TYPE_LINEAR,
+            GammaEncoding::Unknown(x) => Self(x),
+        }
+    }
+}
+
+#[doc(alias = "ctl_pixtx_color_space_t")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ColorSpace {
+    Rec709,
+    Rec2020,
+    AdobeRgb,
+    P3D65,
+    P3Dci,
+    P3D60,
+    /// Described by explicit [`ColorPrimaries`].
+    Custom,
+    /// A color space not known to these bindings.
+    Unknown(i32),
+}
+
+impl From<ctl_pixtx_color_space_t> for ColorSpace {
+    fn from(color_space: ctl_pixtx_color_space_t) -> Self {
+        match color_space {
+            ctl_pixtx_color_space_t::CTL_PIXTX_COLOR_SPACE_REC709 => Self::Rec709,
+            ctl_pixtx_color_space_t::CTL_PIXTX_COLOR_SPACE_REC2020 => Self::Rec2020,
+            ctl_pixtx_color_space_t::CTL_PIXTX_COLOR_SPACE_ADOBE_RGB => Self::AdobeRgb,
+            ctl_pixtx_color_space_t::CTL_PIXTX_COLOR_SPACE_P3_D65 => Self::P3D65,
+            ctl_pixtx_color_space_t::CTL_PIXTX_COLOR_SPACE_P3_DCI => Self::P3Dci,
+            ctl_pixtx_color_space_t::CTL_PIXTX_COLOR_SPACE_P3_D60 => Self::P3D60,
+            ctl_pixtx_color_space_t::CTL_PIXTX_COLOR_SPACE_CUSTOM => Self::Custom,
+            ctl_pixtx_color_space_t(x) => Self::Unknown(x),
+        }
+    }
+}
+
+impl From<ColorSpace> for ctl_pixtx_color_space_t {
+    fn from(color_space: ColorSpace) -> Self {
+        match color_space {
+            ColorSpace::Rec709 => Self::CTL_PIXTX_COLOR_SPACE_REC709,
+            ColorSpace::Rec2020 => Self::CTL_PIXTX_COLOR_SPACE_REC2020,
+            ColorSpace::AdobeRgb => Self::CTL_PIXTX_COLOR_SPACE_ADOBE_RGB,
+            ColorSpace::P3D65 => Self::CTL_PIXTX_COLOR_SPACE_P3_D65,
+            ColorSpace::P3Dci => Self::CTL_PIXTX_COLOR_SPACE_P3_DCI,
+            ColorSpace::P3D60 => Self::CTL_PIXTX_COLOR_SPACE_P3_D60,
+            ColorSpace::Custom => Self::CTL_PIXTX_COLOR_SPACE_CUSTOM,
+            ColorSpace::Unknown(x) => Self(x),
+        }
+    }
+}
+
+impl ColorSpace {
+    /// Primaries of the well-known color spaces.
+    pub fn primaries(self) -> Option<ColorPrimaries> {
+        match self {
+            Self::Rec709 => Some(ColorPrimaries::BT709),
+            Self::Rec2020 => Some(ColorPrimaries::BT2020),
+            Self::AdobeRgb => Some(ColorPrimaries::ADOBE_RGB),
+            Self::P3D65 => Some(ColorPrimaries::DISPLAY_P3),
+            Self::P3Dci => Some(ColorPrimaries::DCI_P3),
+            Self::P3D60 => Some(ColorPrimaries::P3_D60),
+            Self::Custom | Self::Unknown(_) => None,
+        }
+    }
+}
+
+/// Color model of the pixels flowing through the pixel transformation pipeline.
+#[doc(alias = "ctl_pixtx_color_model_t")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PixelColorModel {
+    RgbFullRange,
+    RgbLimitedRange,
+    YCbCr422FullRange,
+    YCbCr422LimitedRange,
+    YCbCr420FullRange,
+    YCbCr420LimitedRange,
+    YCbCr444FullRange,
+    YCbCr444LimitedRange,
+    /// A color model not known to these bindings.
+    Unknown(i32),
+}
+
+impl From<ctl_pixtx_color_model_t> for PixelColorModel {
+    fn from(model: ctl_pixtx_color_model_t) -> Self {
+        match model {
+            ctl_pixtx_color_model_t::CTL_PIXTX_COLOR_MODEL_RGB_FR => Self::RgbFullRange,
+            ctl_pixtx_color_model_t::CTL_PIXTX_COLOR_MODEL_RGB_LR => Self::RgbLimitedRange,
+            ctl_pixtx_color_model_t::CTL_PIXTX_COLOR_MODEL_YCBCR_422_FR => Self::YCbCr422FullRange,
+            ctl_pixtx_color_model_t::CTL_PIXTX_COLOR_MODEL_YCBCR_422_LR => {
+                Self::YCbCr422LimitedRange
+            }
+            ctl_pixtx_color_model_t::CTL_PIXTX_COLOR_MODEL_YCBCR_420_FR => Self::YCbCr420FullRange,
+            ctl_pixtx_color_model_t::CTL_PIXTX_COLOR_MODEL_YCBCR_420_LR => {
+                Self::YCbCr420LimitedRange
+            }
+            ctl_pixtx_color_model_t::CTL_PIXTX_COLOR_MODEL_YCBCR_444_FR => Self::YCbCr444FullRange,
+            ctl_pixtx_color_model_t::CTL_PIXTX_COLOR_MODEL_YCBCR_444_LR => {
+                Self::YCbCr444LimitedRange
+            }
+            ctl_pixtx_color_model_t(x) => Self::Unknown(x),
+        }
+    }
+}
+
+impl From<PixelColorModel> for ctl_pixtx_color_model_t {
+    fn from(model: PixelColorModel) -> Self {
+        match model {
+            PixelColorModel::RgbFullRange => Self::CTL_PIXTX_COLOR_MODEL_RGB_FR,
+            PixelColorModel::RgbLimitedRange => Self::CTL_PIXTX_COLOR_MODEL_RGB_LR,
+            PixelColorModel::YCbCr422FullRange => Self::CTL_PIXTX_COLOR_MODEL_YCBCR_422_FR,
+            PixelColorModel::YCbCr422LimitedRange => Self::CTL_PIXTX_COLOR_MODEL_YCBCR_422_LR,
+            PixelColorModel::YCbCr420FullRange => Self::CTL_PIXTX_COLOR_MODEL_YCBCR_420_FR,
+            PixelColorModel::YCbCr420LimitedRange => Self::CTL_PIXTX_COLOR_MODEL_YCBCR_420_LR,
+            PixelColorModel::YCbCr444FullRange => Self::CTL_PIXTX_COLOR_MODEL_YCBCR_444_FR,
+            PixelColorModel::YCbCr444LimitedRange => Self::CTL_PIXTX_COLOR_MODEL_YCBCR_444_LR,
+            PixelColorModel::Unknown(x) => Self(x),
+        }
+    }
+}
+
+/// CIE 1931 xy chromaticities of the red, green and blue primaries and the white point of an
+/// RGB color space.
+#[doc(alias = "ctl_pixtx_color_primaries_t")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ColorPrimaries {
+    pub red: [f64; 2],
+    pub green: [f64; 2],
+    pub blue: [f64; 2],
+    pub white: [f64; 2],
+}
+
+const D65: [f64; 2] = [0.3127, 0.3290];
+
+impl ColorPrimaries {
+    /// ITU-R BT.709, also used by sRGB.
+    pub const BT709: Self = Self {
+        red: [0.640, 0.330],
+        green: [0.300, 0.600],
+        blue: [0.150, 0.060],
+        white: D65,
+    };
+
+    /// SMPTE RP 431-2 with the DCI white point.
+    pub const DCI_P3: Self = Self {
+        red: [0.680, 0.320],
+        green: [0.265, 0.690],
+        blue: [0.150, 0.060],
+        white: [0.314, 0.351],
+    };
+
+    /// P3 primaries with a D65 white point.
+    pub const DISPLAY_P3: Self = Self {
+        white: D65,
+        ..Self::DCI_P3
+    };
+
+    /// P3 primaries with a D60 white point.
+    pub const P3_D60: Self = Self {
+        white: [0.32168, 0.33767],
+        ..Self::DCI_P3
+    };
+
+    /// ITU-R BT.2020, also used by BT.2100.
+    pub const BT2020: Self = Self {
+        red: [0.708, 0.292],
+        green: [0.170, 0.797],
+        blue: [0.131, 0.046],
+        white: D65,
+    };
+
+    pub const ADOBE_RGB: Self = Self {
+        red: [0.640, 0.330],
+        green: [0.210, 0.710],
+        blue: [0.150, 0.060],
+        white: D65,
+    };
+
+    /// Matrix converting linear RGB in this color space to CIE XYZ, normalized so that white
+    /// has `Y = 1`.
+    pub fn rgb_to_xyz(&self) -> [[f64; 3]; 3] {
+        let primaries = [
+            xy_to_xyz(self.red),
+            xy_to_xyz(self.green),
+            xy_to_xyz(self.blue),
+        ];
+        let m = transpose(primaries);
+        let scale = mul_vec(&invert(&m), xy_to_xyz(self.white));
+
+        let mut out = m;
+        for row in &mut out {
+            for (value, scale) in row.iter_mut().zip(scale) {
+                *value *= scale;
+            }
+        }
+        out
+    }
+
+    /// Matrix converting linear RGB in this color space to linear RGB in `target`. Differing
+    /// white points are adapted with the Bradford transform.
+    pub fn conversion_matrix(&self, target: &ColorPrimaries) -> Matrix3x3 {
+        let mut to_xyz = self.rgb_to_xyz();
+        if self.white != target.white {
+            to_xyz = mul(&bradford(self.white, target.white), &to_xyz);
+        }
+        Matrix3x3::new(mul(&invert(&target.rgb_to_xyz()), &to_xyz))
+    }
+}
+
+impl From<ctl_pixtx_color_primaries_t> for ColorPrimaries {
+    fn from(primaries: ctl_pixtx_color_primaries_t) -> Self {
+        Self {
+            red: [primaries.xR, primaries.yR],
+            green: [primaries.xG, primaries.yG],
+            blue: [primaries.xB, primaries.yB],
+            white: [primaries.xW, primaries.yW],
+        }
+    }
+}
+
+impl From<ColorPrimaries> for ctl_pixtx_color_primaries_t {
+    fn from(primaries: ColorPrimaries) -> Self {
+        Self {
+            Size: std::mem::size_of::<ctl_pixtx_color_primaries_t>() as u32,
+            Version: 0,
+            xR: primaries.red[0],
+            yR: primaries.red[1],
+            xG: primaries.green[0],
+            yG: primaries.green[1],
+            xB: primaries.blue[0],
+            yB: primaries.blue[1],
+            xW: primaries.white[0],
+            yW: primaries.white[1],
+        }
+    }
+}
+
+fn xy_to_xyz([x, y]: [f64; 2]) -> [f64; 3] {
+    [x / y, 1.0, (1.0 - x - y) / y]
+}
+
+fn transpose(m: [[f64; 3]; 3]) -> [[f64; 3]; 3] {
+    let mut out = [[0.0; 3]; 3];
+    for (i, row) in m.iter().enumerate() {
+        for (j, value) in row.iter().enumerate() {
+            out[j][i] = *value;
+        }
+    }
+    out
+}
+
+fn mul(a: &[[f64; 3]; 3], b: &[[f64; 3]; 3]) -> [[f64; 3]; 3] {
+    let mut out = [[0.0; 3]; 3];
+    for (i, row) in out.iter_mut().enumerate() {
+        for (j, value) in row.iter_mut().enumerate() {
+            *value = (0..3).map(|k| a[i][k] * b[k][j]).sum();
+        }
+    }
+    out
+}
+
+fn mul_vec(m: &[[f64; 3]; 3], v: [f64; 3]) -> [f64; 3] {
+    m.map(|row| row[0] * v[0] + row[1] * v[1] + row[2] * v[2])
+}
+
+fn invert(m: &[[f64; 3]; 3]) -> [[f64; 3]; 3] {
+    let cofactor =
+        |r0: usize, r1: usize, c0: usize, c1: usize| m[r0][c0] * m[r1][c1] - m[r0][c1] * m[r1][c0];
+
+    let adjugate = [
+        [
+            cofactor(1, 2, 1, 2),
+            -cofactor(0, 2, 1, 2),
+            cofactor(0, 1, 1, 2),
+        ],
+        [
+            -cofactor(1, 2, 0, 2),
+            cofactor(0, 2, 0, 2),
+            -cofactor(0, 1, 0, 2),
+        ],
+        [
+            cofactor(1, 2, 0, 1),
+            -cofactor(0, 2, 0, 1),
+            cofactor(0, 1, 0, 1),
+        ],
+    ];
+    let det = m[0][0] * adjugate[0][0] + m[0][1] * adjugate[1][0] + m[0][2] * adjugate[2][0];
+
+    adjugate.map(|row| row.map(|value| value / det))
+}
+
+/// Bradford chromatic adaptation from the `source` to the `target` white point, in XYZ.
+fn bradford(source: [f64; 2], target: [f64; 2]) -> [[f64; 3]; 3] {
+    const BRADFORD: [[f64; 3]; 3] = [
+        [0.8951, 0.2664, -0.1614],
+        [-0.7502, 1.7135, 0.0367],
+        [0.0389, -0.0685, 1.0296],
+    ];
+
+    let source = mul_vec(&BRADFORD, xy_to_xyz(source));
+    let target = mul_vec(&BRADFORD, xy_to_xyz(target));
+    let mut scale = [[0.0; 3]; 3];
+    for i in 0..3 {
+        scale[i][i] = target[i] / source[i];
+    }
+
+    mul(&invert(&BRADFORD), &mul(&scale, &BRADFORD))
+}
+
+/// Transfer function between non-linear signal values and linear light, both normalized to
+/// `0.0..=1.0`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TransferFunction {
+    Linear,
+    /// IEC 61966-2-1 piecewise sRGB curve.
+    Srgb,
+    /// ITU-R BT.1886 reference EOTF for a display with zero black level, i.e. gamma 2.4.
+    Bt1886,
+    /// SMPTE ST 2084 perceptual quantizer, with linear light normalized to 10000 cd/m².
+    Pq,
+    /// ITU-R BT.2100 hybrid log-gamma, with scene linear light.
+    Hlg,
+    /// Pure power law with the given exponent, which has to be a finite positive number; see
+    /// [`Self::power()`].
+    Power(f64),
+}
+
+const PQ_M1: f64 = 2610.0 / 16384.0;
+const PQ_M2: f64 = 2523.0 / 4096.0 * 128.0;
+const PQ_C1: f64 = 3424.0 / 4096.0;
+const PQ_C2: f64 = 2413.0 / 4096.0 * 32.0;
+const PQ_C3: f64 = 2392.0 / 4096.0 * 32.0;
+
+const HLG_A: f64 = 0.17883277;
+const HLG_B: f64 = 0.28466892;
+const HLG_C: f64 = 0.55991073;
+
+impl TransferFunction {
+    /// Pure power law with the given exponent. Returns [`None`] unless `gamma` is a finite
+    /// positive number.
+    pub fn power(gamma: f64) -> Option<Self> {
+        Some(Self::Power(gamma)).filter(|f| f.is_valid())
+    }
+
+    /// Whether the exponent of a [`Self::Power`] function is a finite positive number; always
+    /// `true` for other functions.
+    pub fn is_valid(self) -> bool {
+        match self {
+            Self::Power(gamma) => gamma.is_finite() && gamma > 0.0,
+            _ => true,
+        }
+    }
+
+    /// Transfer function matching a pixel format's gamma encoding, if known.
+    pub fn from_encoding(encoding: GammaEncoding) -> Option<Self> {
+        match encoding {
+            GammaEncoding::Srgb => Some(Self::Srgb),
+            GammaEncoding::Rec709 => Some(Self::Bt1886),
+            GammaEncoding::St2084 => Some(Self::Pq),
+            GammaEncoding::Hlg => Some(Self::Hlg),
+            GammaEncoding::Linear => Some(Self::Linear),
+            GammaEncoding::Unknown(_) => None,
+        }
+    }
+
+    /// Convert a signal value to linear light.
+    pub fn to_linear(self, signal: f64) -> f64 {
+        let v = signal.clamp(0.0, 1.0);
+        match self {
+            Self::Linear => v,
+            Self::Srgb => {
+                if v <= 0.04045 {
+                    v / 12.92
+                } else {
+                    ((v + 0.055) / 1.055).powf(2.4)
+                }
+            }
+            Self::Bt1886 => v.powf(2.4),
+            Self::Pq => {
+                let p = v.powf(1.0 / PQ_M2);
+                ((p - PQ_C1).max(0.0) / (PQ_C2 - PQ_C3 * p)).powf(1.0 / PQ_M1)
+            }
+            Self::Hlg => {
+                if v <= 0.5 {
+                    v * v / 3.0
+                } else {
+                    (((v - HLG_C) / HLG_A).exp() + HLG_B) / 12.0
+                }
+            }
+            Self::Power(gamma) => v.powf(gamma),
+        }
+    }
+
+    /// Convert linear light to a signal value; the inverse of [`Self::to_linear()`].
+    pub fn from_linear(self, linear: f64) -> f64 {
+        let l = linear.clamp(0.0, 1.0);
+        match self {
+            Self::Linear => l,
+            Self::Srgb => {
+                if l <= 0.0031308 {
+                    l * 12.92
+                } else {
+                    1.055 * l.powf(1.0 / 2.4) - 0.055
+                }
+            }
+            Self::Bt1886 => l.powf(1.0 / 2.4),
+            Self::Pq => {
+                let p = l.powf(PQ_M1);
+                ((PQ_C1 + PQ_C2 * p) / (1.0 + PQ_C3 * p)).powf(PQ_M2)
+            }
+            Self::Hlg => {
+                if l <= 1.0 / 12.0 {
+                    (3.0 * l).sqrt()
+                } else {
+                    HLG_A * (12.0 * l - HLG_B).ln() + HLG_C
+                }
+            }
+            Self::Power(gamma) => l.powf(1.0 / gamma),
+        }
+    }
+
+    /// Grey scale LUT with `num_samples` uniformly spaced samples that decodes signal values
+    /// to linear light, e.g. for a degamma block. Returns [`None`] unless
+    /// [`Self::is_valid()`].
+    pub fn linearize_lut(self, num_samples: usize) -> Option<OneDLut> {
+        self.is_valid()
+            .then(|| OneDLut::grey(uniform(num_samples).map(|x| self.to_linear(x)).collect()))
+    }
+
+    /// Grey scale LUT with `num_samples` uniformly spaced samples that encodes linear light
+    /// to signal values, e.g. for a gamma block. Returns [`None`] unless
+    /// [`Self::is_valid()`].
+    pub fn encode_lut(self, num_samples: usize) -> Option<OneDLut> {
+        self.is_valid()
+            .then(|| OneDLut::grey(uniform(num_samples).map(|x| self.from_linear(x)).collect()))
+    }
+}
+
+fn uniform(num_samples: usize) -> impl Iterator<Item = f64> {
+    let scale = num_samples.saturating_sub(1).max(1) as f64;
+    (0..num_samples).map(move |i| i as f64 / scale)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_vec_eq(actual: [f64; 3], expected: [f64; 3], tolerance: f64) {
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < tolerance, "{actual:?} != {expected:?}");
+        }
+    }
+
+    fn assert_matrix_eq(actual: &[[f64; 3]; 3], expected: &[[f64; 3]; 3], tolerance: f64) {
+        for (actual, expected) in actual.iter().zip(expected) {
+            assert_vec_eq(*actual, *expected, tolerance);
+        }
+    }
+
+    #[test]
+    fn bt709_to_bt2020() {
+        // ITU-R BT.2087, equation 4
+        let expected = [
+            [0.6274, 0.3293, 0.0433],
+            [0.0691, 0.9195, 0.0114],
+            [0.0164, 0.0880, 0.8956],
+        ];
+        let matrix = ColorPrimaries::BT709.conversion_matrix(&ColorPrimaries::BT2020);
+        assert_matrix_eq(&matrix.matrix, &expected, 1e-4);
+        assert!(!matrix.has_offsets());
+    }
+
+    #[test]
+    fn identity_conversion() {
+        for primaries in [
+            ColorPrimaries::BT709,
+            ColorPrimaries::BT2020,
+            ColorPrimaries::DCI_P3,
+            ColorPrimaries::ADOBE_RGB,
+        ] {
+            let matrix = primaries.conversion_matrix(&primaries);
+            assert_matrix_eq(&matrix.matrix, &Matrix3x3::IDENTITY.matrix, 1e-12);
+        }
+    }
+
+    #[test]
+    fn rgb_to_xyz_white() {
+        let white = mul_vec(&ColorPrimaries::BT709.rgb_to_xyz(), [1.0; 3]);
+        assert_vec_eq(white, xy_to_xyz(D65), 1e-12);
+    }
+
+    #[test]
+    fn bradford_d65_to_d50() {
+        // Lindbloom's Bradford matrix, computed from slightly different XYZ white points
+        let expected = [
+            [1.0478112, 0.0228866, -0.0501270],
+            [0.0295424, 0.9904844, -0.0170491],
+            [-0.0092345, 0.0150436, 0.7521316],
+        ];
+        let d50 = [0.3457, 0.3585];
+        assert_matrix_eq(&bradford(D65, d50), &expected, 1e-3);
+
+        let adapted = mul_vec(&bradford(D65, d50), xy_to_xyz(D65));
+        assert_vec_eq(adapted, xy_to_xyz(d50), 1e-12);
+    }
+
+    #[test]
+    fn known_values() {
+        assert!((TransferFunction::Srgb.to_linear(0.5) - 0.214041).abs() < 1e-6);
+        assert!((TransferFunction::Srgb.from_linear(0.0031308) - 0.0404492).abs() < 1e-6);
+        // 100 cd/m² in PQ
+        assert!((TransferFunction::Pq.from_linear(0.01) - 0.508078).abs() < 1e-6);
+        assert!((TransferFunction::Pq.to_linear(1.0) - 1.0).abs() < 1e-12);
+        assert!((TransferFunction::Hlg.from_linear(1.0 / 12.0) - 0.5).abs() < 1e-12);
+        assert!((TransferFunction::Hlg.from_linear(1.0) - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn round_trips() {
+        for function in [
+            TransferFunction::Linear,
+            TransferFunction::Srgb,
+            TransferFunction::Bt1886,
+            TransferFunction::Pq,
+            TransferFunction::Hlg,
+            TransferFunction::Power(2.2),
+        ] {
+            for x in uniform(101) {
+                let decoded = function.from_linear(function.to_linear(x));
+                let encoded = function.to_linear(function.from_linear(x));
+                // PQ maps a signal of 0.0 to a tiny non-zero linear value on the way back
+                assert!((decoded - x).abs() < 1e-6, "{function:?} {x} {decoded}");
+                assert!((encoded - x).abs() < 1e-6, "{function:?} {x} {encoded}");
+            }
+        }
+    }
+
+    #[test]
+    fn power() {
+        assert_eq!(
+            TransferFunction::power(2.2),
+            Some(TransferFunction::Power(2.2))
+        );
+        for gamma in [0.0, -2.2, f64::NAN, f64::INFINITY] {
+            assert_eq!(TransferFunction::power(gamma), None);
+            assert!(!TransferFunction::Power(gamma).is_valid());
+            assert_eq!(TransferFunction::Power(gamma).linearize_lut(16), None);
+            assert_eq!(TransferFunction::Power(gamma).encode_lut(16), None);
+        }
+    }
+
+    #[test]
+    fn luts() {
+        let lut = TransferFunction::Power(2.0).linearize_lut(3).unwrap();
+        assert_eq!(lut, OneDLut::grey(vec![0.0, 0.25, 1.0]));
+        let lut = TransferFunction::Power(0.5).encode_lut(3).unwrap();
+        assert_eq!(lut, OneDLut::grey(vec![0.0, 0.25, 1.0]));
+    }
+}
@@ -0,0 +1,661 @@
+use std::fmt;
+
+use crate::{
+    device_adapter::DeviceAdapter,
+    display::DisplayOutput,
+    error::{Error, Result},
+    ffi::{
+        ctl_child_display_target_mode_t, ctl_combined_display_args_t,
+        ctl_combined_display_child_info_t, ctl_combined_display_optype_t,
+        ctl_display_orientation_t, ctl_display_output_handle_t, ctl_rect_t,
+    },
+};
+
+/// Maximum number of child displays in a combined display.
+pub const MAX_COMBINED_DISPLAY_CHILDREN: usize = 16;
+
+#[doc(alias = "ctl_rect_t")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rect {
+    pub left: i32,
+    pub top: i32,
+    pub right: i32,
+    pub bottom: i32,
+}
+
+impl Rect {
+    /// Returns [`None`] when the right or bottom edge does not fit in an [`i32`].
+    pub fn new(left: i32, top: i32, width: u32, height: u32) -> Option<Self> {
+        Some(Self {
+            left,
+            top,
+            right: left.checked_add(i32::try_from(width).ok()?)?,
+            bottom: top.checked_add(i32::try_from(height).ok()?)?,
+        })
+    }
+
+    pub fn width(&self) -> i64 {
+        self.right as i64 - self.left as i64
+    }
+
+    pub fn height(&self) -> i64 {
+        self.bottom as i64 - self.top as i64
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.width() <= 0 || self.height() <= 0
+    }
+
+    pub fn area(&self) -> i64 {
+        if self.is_empty() {
+            0
+        } else {
+            self.width() * self.height()
+        }
+    }
+
+    /// Whether both rectangles share any pixels; touching edges do not count.
+    pub fn overlaps(&self, other: &Rect) -> bool {
+        self.left < other.right
+            && other.left < self.right
+            && self.top < other.bottom
+            && other.top < self.bottom
+    }
+
+    pub fn contains(&self, other: &Rect) -> bool {
+        other.left >= self.left
+            && other.top >= self.top
+            && other.right <= self.right
+            && other.bottom <= self.bottom
+    }
+}
+
+impl From<ctl_rect_t> for Rect {
+    fn from(rect: ctl_rect_t) -> Self {
+        Self {
+            left: rect.Left,
+            top: rect.Top,
+            right: rect.Right,
+            bottom: rect.Bottom,
+        }
+    }
+}
+
+impl From<Rect> for ctl_rect_t {
+    fn from(rect: Rect) -> Self {
+        Self {
+            Left: rect.left,
+            Top: rect.top,
+            Right: rect.right,
+            Bottom: rect.bottom,
+        }
+    }
+}
+
+#[doc(alias = "ctl_display_orientation_t")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DisplayOrientation {
+    #[default]
+    Deg0,
+    Deg90,
+    Deg180,
+    Deg270,
+    /// An orientation not known to these bindings.
+    Unknown(i32),
+}
+
+impl From<ctl_display_orientation_t> for DisplayOrientation {
+    fn from(orientation: ctl_display_orientation_t) -> Self {
+        match orientation {
+            ctl_display_orientation_t::CTL_DISPLAY_ORIENTATION_0 => Self::Deg0,
+            ctl_display_orientation_t::CTL_DISPLAY_ORIENTATION_90 => Self::Deg90,
+            ctl_display_orientation_t::CTL_DISPLAY_ORIENTATION_180 => Self::Deg180,
+            ctl_display_orientation_t::CTL_DISPLAY_ORIENTATION_270 => Self::Deg270,
+            ctl_display_orientation_t(x) => Self::Unknown(x),
+        }
+    }
+}
+
+impl From<DisplayOrientation> for ctl_display_orientation_t {
+    fn from(orientation: DisplayOrientation) -> Self {
+        match orientation {
+            DisplayOrientation::Deg0 => Self::CTL_DISPLAY_ORIENTATION_0,
+            DisplayOrientation::Deg90 => Self::CTL_DISPLAY_ORIENTATION_90,
+            DisplayOrientation::Deg180 => Self::CTL_DISPLAY_ORIENTATION_180,
+            DisplayOrientation::Deg270 => Self::CTL_DISPLAY_ORIENTATION_270,
+            DisplayOrientation::Unknown(x) => Self(x),
+        }
+    }
+}
+
+/// Mode a child display is driven at within a combined display.
+#[doc(alias = "ctl_child_display_target_mode_t")]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChildTargetMode {
+    pub width: u32,
+    pub height: u32,
+    pub refresh_rate: f32,
+}
+
+impl From<ctl_child_display_target_mode_t> for ChildTargetMode {
+    fn from(mode: ctl_child_display_target_mode_t) -> Self {
+        Self {
+            width: mode.Width,
+            height: mode.Height,
+            refresh_rate: mode.RefreshRate,
+        }
+    }
+}
+
+impl From<ChildTargetMode> for ctl_child_display_target_mode_t {
+    fn from(mode: ChildTargetMode) -> Self {
+        Self {
+            Width: mode.width,
+            Height: mode.height,
+            RefreshRate: mode.refresh_rate,
+            ReservedFields: [0; 4],
+        }
+    }
+}
+
+#[doc(alias = "ctl_combined_display_child_info_t")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CombinedDisplayChild {
+    /// Compare against [`DisplayOutput::display_output_handle()`] to find the output.
+    pub display_output_handle: ctl_display_output_handle_t,
+    /// Region of the combined framebuffer shown on this child.
+    pub fb_src: Rect,
+    /// Region of the child's target mode the source region is scaled into.
+    pub fb_pos: Rect,
+    /// Only 0 and 180 degrees are supported by the driver.
+    pub orientation: DisplayOrientation,
+    pub target_mode: ChildTargetMode,
+}
+
+impl CombinedDisplayChild {
+    /// Show `fb_src` on the full area of `output`, at the output's current mode.
+    ///
+    /// Returns [`None`] when the active size of the mode does not fit in a [`Rect`].
+    pub fn new(output: &DisplayOutput, fb_src: Rect) -> Option<Self> {
+        let timing = output.timing();
+        let target_mode = ChildTargetMode {
+            width: timing.h_active,
+            height: timing.v_active,
+            refresh_rate: timing.refresh_rate,
+        };
+
+        Some(Self {
+            display_output_handle: output.display_output_handle,
+            fb_src,
+            fb_pos: Rect::new(0, 0, target_mode.width, target_mode.height)?,
+            orientation: DisplayOrientation::Deg0,
+            target_mode,
+        })
+    }
+}
+
+impl From<ctl_combined_display_child_info_t> for CombinedDisplayChild {
+    fn from(child: ctl_combined_display_child_info_t) -> Self {
+        Self {
+            display_output_handle: child.hDisplayOutput,
+            fb_src: child.FbSrc.into(),
+            fb_pos: child.FbPos.into(),
+            orientation: child.DisplayOrientation.into(),
+            target_mode: child.TargetMode.into(),
+        }
+    }
+}
+
+impl From<CombinedDisplayChild> for ctl_combined_display_child_info_t {
+    fn from(child: CombinedDisplayChild) -> Self {
+        Self {
+            hDisplayOutput: child.display_output_handle,
+            FbSrc: child.fb_src.into(),
+            FbPos: child.fb_pos.into(),
+            DisplayOrientation: child.orientation.into(),
+            TargetMode: child.target_mode.into(),
+        }
+    }
+}
+
+/// Layout of a combined display: a single desktop spanning multiple child displays.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CombinedDisplay {
+    pub width: u32,
+    pub height: u32,
+    pub children: Vec<CombinedDisplayChild>,
+}
+
+/// Reasons for which a layout is rejected by [`CombinedDisplay::validate()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CombinedDisplayLayoutError {
+    NoChildren,
+    /// More than [`MAX_COMBINED_DISPLAY_CHILDREN`] children were given.
+    TooManyChildren(usize),
+    /// The combined desktop size does not fit in a [`Rect`].
+    DesktopTooLarge,
+    /// A child's target mode size does not fit in a [`Rect`].
+    TargetModeTooLarge {
+        child: usize,
+    },
+    /// A child has an empty source or target rectangle.
+    EmptyRect {
+        child: usize,
+    },
+    /// A child's source rectangle extends past the combined desktop.
+    SourceOutOfBounds {
+        child: usize,
+    },
+    /// A child's target rectangle extends past its target mode.
+    TargetOutOfBounds {
+        child: usize,
+    },
+    /// The source rectangles of two children overlap.
+    Overlap {
+        first: usize,
+        second: usize,
+    },
+    /// The source rectangles do not cover the entire combined desktop.
+    Gap,
+}
+
+impl fmt::Display for CombinedDisplayLayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoChildren => f.write_str("combined display has no children"),
+            Self::TooManyChildren(count) => write!(
+                f,
+                "combined display has {count} children, at most {MAX_COMBINED_DISPLAY_CHILDREN} are supported"
+            ),
+            Self::DesktopTooLarge => f.write_str("combined desktop size is out of range"),
+            Self::TargetModeTooLarge { child } => {
+                write!(f, "target mode size of child {child} is out of range")
+            }
+            Self::EmptyRect { child } => write!(f, "child {child} has an empty rectangle"),
+            Self::SourceOutOfBounds { child } => write!(
+                f,
+                "source rectangle of child {child} extends past the combined desktop"
+            ),
+            Self::TargetOutOfBounds { child } => write!(
+                f,
+                "target rectangle of child {child} extends past its target mode"
+            ),
+            Self::Overlap { first, second } => write!(
+                f,
+                "source rectangles of children {first} and {second} overlap"
+            ),
+            Self::Gap => f.write_str("source rectangles do not cover the combined desktop"),
+        }
+    }
+}
+
+impl std::error::Error for CombinedDisplayLayoutError {}
+
+/// Error returned by combined display operations that validate their input before calling
+/// into IGCL.
+#[derive(Debug)]
+pub enum CombinedDisplayError {
+    Invalid(CombinedDisplayLayoutError),
+    Igcl(Error),
+}
+
+impl fmt::Display for CombinedDisplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Invalid(error) => write!(f, "invalid combined display layout: {error}"),
+            Self::Igcl(error) => fmt::Display::fmt(error, f),
+        }
+    }
+}
+
+impl std::error::Error for CombinedDisplayError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Invalid(error) => Some(error),
+            Self::Igcl(error) => Some(error),
+        }
+    }
+}
+
+impl From<CombinedDisplayLayoutError> for CombinedDisplayError {
+    fn from(error: CombinedDisplayLayoutError) -> Self {
+        Self::Invalid(error)
+    }
+}
+
+impl From<Error> for CombinedDisplayError {
+    fn from(error: Error) -> Self {
+        Self::Igcl(error)
+    }
+}
+
+impl CombinedDisplay {
+    /// Check that the source rectangles tile the combined desktop exactly, without overlaps
+    /// or gaps, and that every target rectangle fits within its child's target mode.
+    pub fn validate(&self) -> Result<(), CombinedDisplayLayoutError> {
+        if self.children.is_empty() {
+            return Err(CombinedDisplayLayoutError::NoChildren);
+        }
+        if self.children.len() > MAX_COMBINED_DISPLAY_CHILDREN {
+            return Err(CombinedDisplayLayoutError::TooManyChildren(
+                self.children.len(),
+            ));
+        }
+
+        let desktop = Rect::new(0, 0, self.width, self.height)
+            .ok_or(CombinedDisplayLayoutError::DesktopTooLarge)?;
+
+        for (i, child) in self.children.iter().enumerate() {
+            if child.fb_src.is_empty() || child.fb_pos.is_empty() {
+                return Err(CombinedDisplayLayoutError::EmptyRect { child: i });
+            }
+            if !desktop.contains(&child.fb_src) {
+                return Err(CombinedDisplayLayoutError::SourceOutOfBounds { child: i });
+            }
+
+            let target = Rect::new(0, 0, child.target_mode.width, child.target_mode.height)
+                .ok_or(CombinedDisplayLayoutError::TargetModeTooLarge { child: i })?;
+            if !target.contains(&child.fb_pos) {
+                return Err(CombinedDisplayLayoutError::TargetOutOfBounds { child: i });
+            }
+
+            for (j, other) in self.children.iter().enumerate().skip(i + 1) {
+                if child.fb_src.overlaps(&other.fb_src) {
+                    return Err(CombinedDisplayLayoutError::Overlap {
+                        first: i,
+                        second: j,
+                    });
+                }
+            }
+        }
+
+        // With every rectangle inside the desktop and none overlapping, they cover it
+        // exactly when their areas add up to the desktop's
+        let covered: i64 = self.children.iter().map(|c| c.fb_src.area()).sum();
+        if covered != desktop.area() {
+            return Err(CombinedDisplayLayoutError::Gap);
+        }
+
+        Ok(())
+    }
+}
+
+impl DeviceAdapter {
+    fn get_set_combined_display(
+        &self,
+        op_type: ctl_combined_display_optype_t,
+        args: &mut ctl_combined_display_args_t,
+        children: &mut [ctl_combined_display_child_info_t],
+    ) -> Result<()> {
+        args.Size = std::mem::size_of::<ctl_combined_display_args_t>() as u32;
+        args.Version = 0;
+        args.OpType = op_type;
+        args.NumOutputs = children.len() as u8;
+        args.pChildInfo = if children.is_empty() {
+            std::ptr::null_mut()
+        } else {
+            children.as_mut_ptr()
+        };
+
+        Error::from_result(unsafe {
+            self.control_lib
+                .ctlGetSetCombinedDisplay(self.device_adapter_handle, args)
+        })
+    }
+
+    fn combined_display_args(
+        &self,
+        op_type: ctl_combined_display_optype_t,
+        layout: &CombinedDisplay,
+    ) -> Result<ctl_combined_display_args_t, CombinedDisplayError> {
+        layout.validate()?;
+
+        let mut args = ctl_combined_display_args_t {
+            CombinedDesktopWidth: layout.width,
+            CombinedDesktopHeight: layout.height,
+            ..Default::default()
+        };
+        let mut children: Vec<ctl_combined_display_child_info_t> =
+            layout.children.iter().map(|&c| c.into()).collect();
+        self.get_set_combined_display(op_type, &mut args, &mut children)?;
+
+        Ok(args)
+    }
+
+    /// Check whether the driver can form `layout` on this adapter, without applying it.
+    #[doc(alias = "ctlGetSetCombinedDisplay")]
+    #[doc(alias = "CTL_COMBINED_DISPLAY_OPTYPE_IS_SUPPORTED_CONFIG")]
+    pub fn is_combined_display_supported(
+        &self,
+        layout: &CombinedDisplay,
+    ) -> Result<bool, CombinedDisplayError> {
+        let args = self.combined_display_args(
+            ctl_combined_display_optype_t::CTL_COMBINED_DISPLAY_OPTYPE_IS_SUPPORTED_CONFIG,
+            layout,
+        )?;
+        Ok(args.IsSupported)
+    }
+
+    /// Set up and enable `layout`, returning the handle of the resulting combined display
+    /// output. The first child becomes the primary display.
+    #[doc(alias = "CTL_COMBINED_DISPLAY_OPTYPE_ENABLE")]
+    pub fn enable_combined_display(
+        &self,
+        layout: &CombinedDisplay,
+    ) -> Result<ctl_display_output_handle_t, CombinedDisplayError> {
+        let args = self.combined_display_args(
+            ctl_combined_display_optype_t::CTL_COMBINED_DISPLAY_OPTYPE_ENABLE,
+            layout,
+        )?;
+        Ok(args.hCombinedDisplayOutput)
+    }
+
+    /// Tear down the combined display identified by `combined_display_output_handle`.
+    #[doc(alias = "CTL_COMBINED_DISPLAY_OPTYPE_DISABLE")]
+    pub fn disable_combined_display(
+        &self,
+        combined_display_output_handle: ctl_display_output_handle_t,
+    ) -> Result<()> {
+        let mut args = ctl_combined_display_args_t {
+            hCombinedDisplayOutput: combined_display_output_handle,
+            ..Default::default()
+        };
+        self.get_set_combined_display(
+            ctl_combined_display_optype_t::CTL_COMBINED_DISPLAY_OPTYPE_DISABLE,
+            &mut args,
+            &mut [],
+        )
+    }
+
+    /// Query the layout of the combined display identified by
+    /// `combined_display_output_handle`.
+    #[doc(alias = "CTL_COMBINED_DISPLAY_OPTYPE_QUERY_CONFIG")]
+    pub fn combined_display(
+        &self,
+        combined_display_output_handle: ctl_display_output_handle_t,
+    ) -> Result<CombinedDisplay> {
+        let op_type = ctl_combined_display_optype_t::CTL_COMBINED_DISPLAY_OPTYPE_QUERY_CONFIG;
+        let mut args = ctl_combined_display_args_t {
+            hCombinedDisplayOutput: combined_display_output_handle,
+            ..Default::default()
+        };
+
+        // The first pass only returns the number of children
+        self.get_set_combined_display(op_type, &mut args, &mut [])?;
+
+        let mut children =
+            vec![ctl_combined_display_child_info_t::default(); args.NumOutputs as usize];
+        self.get_set_combined_display(op_type, &mut args, &mut children)?;
+        children.truncate(args.NumOutputs as usize);
+
+        Ok(CombinedDisplay {
+            width: args.CombinedDesktopWidth,
+            height: args.CombinedDesktopHeight,
+            children: children
+                .into_iter()
+                .map(CombinedDisplayChild::from)
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(left: i32, top: i32, width: u32, height: u32) -> Rect {
+        Rect::new(left, top, width, height).unwrap()
+    }
+
+    fn child(fb_src: Rect) -> CombinedDisplayChild {
+        CombinedDisplayChild {
+            display_output_handle: std::ptr::null_mut(),
+            fb_src,
+            fb_pos: rect(0, 0, 1920, 1080),
+            orientation: DisplayOrientation::Deg0,
+            target_mode: ChildTargetMode {
+                width: 1920,
+                height: 1080,
+                refresh_rate: 60.0,
+            },
+        }
+    }
+
+    /// Two 1920x1080 displays side by side.
+    fn side_by_side() -> CombinedDisplay {
+        CombinedDisplay {
+            width: 3840,
+            height: 1080,
+            children: vec![
+                child(rect(0, 0, 1920, 1080)),
+                child(rect(1920, 0, 1920, 1080)),
+            ],
+        }
+    }
+
+    #[test]
+    fn rect_new() {
+        assert_eq!(
+            Rect::new(-10, 20, 30, 40),
+            Some(Rect {
+                left: -10,
+                top: 20,
+                right: 20,
+                bottom: 60
+            })
+        );
+        assert_eq!(Rect::new(0, 0, u32::MAX, 1), None);
+        assert_eq!(Rect::new(0, 0, 1, i32::MAX as u32 + 1), None);
+        assert_eq!(Rect::new(i32::MAX, 0, 1, 1), None);
+        assert!(Rect::new(i32::MIN, 0, i32::MAX as u32, 1).is_some());
+    }
+
+    #[test]
+    fn rect_overlaps() {
+        let a = rect(0, 0, 10, 10);
+        assert!(a.overlaps(&rect(9, 9, 10, 10)));
+        assert!(!a.overlaps(&rect(10, 0, 10, 10)));
+        assert!(!a.overlaps(&rect(0, 10, 10, 10)));
+        assert!(a.contains(&rect(2, 2, 8, 8)));
+        assert!(!a.contains(&rect(2, 2, 9, 8)));
+    }
+
+    #[test]
+    fn valid() {
+        assert_eq!(side_by_side().validate(), Ok(()));
+    }
+
+    #[test]
+    fn children_count() {
+        let mut layout = side_by_side();
+        layout.children.clear();
+        assert_eq!(
+            layout.validate(),
+            Err(CombinedDisplayLayoutError::NoChildren)
+        );
+
+        layout.children = vec![child(rect(0, 0, 1, 1)); MAX_COMBINED_DISPLAY_CHILDREN + 1];
+        assert_eq!(
+            layout.validate(),
+            Err(CombinedDisplayLayoutError::TooManyChildren(
+                MAX_COMBINED_DISPLAY_CHILDREN + 1
+            ))
+        );
+    }
+
+    #[test]
+    fn overlap() {
+        let mut layout = side_by_side();
+        layout.children[1].fb_src = rect(1900, 0, 1920, 1080);
+        layout.width = 3820;
+        assert_eq!(
+            layout.validate(),
+            Err(CombinedDisplayLayoutError::Overlap {
+                first: 0,
+                second: 1
+            })
+        );
+    }
+
+    #[test]
+    fn gap() {
+        let mut layout = side_by_side();
+        layout.width = 3860;
+        layout.children[1].fb_src = rect(1940, 0, 1920, 1080);
+        assert_eq!(layout.validate(), Err(CombinedDisplayLayoutError::Gap));
+    }
+
+    #[test]
+    fn out_of_bounds() {
+        let mut layout = side_by_side();
+        layout.children[1].fb_src = rect(1920, 0, 1920, 1081);
+        assert_eq!(
+            layout.validate(),
+            Err(CombinedDisplayLayoutError::SourceOutOfBounds { child: 1 })
+        );
+
+        let mut layout = side_by_side();
+        layout.children[0].fb_src = rect(-1, 0, 1920, 1080);
+        assert_eq!(
+            layout.validate(),
+            Err(CombinedDisplayLayoutError::SourceOutOfBounds { child: 0 })
+        );
+
+        let mut layout = side_by_side();
+        layout.children[1].fb_pos = rect(0, 0, 2560, 1440);
+        assert_eq!(
+            layout.validate(),
+            Err(CombinedDisplayLayoutError::TargetOutOfBounds { child: 1 })
+        );
+    }
+
+    #[test]
+    fn empty_rect() {
+        let mut layout = side_by_side();
+        layout.children[0].fb_pos = rect(0, 0, 0, 1080);
+        assert_eq!(
+            layout.validate(),
+            Err(CombinedDisplayLayoutError::EmptyRect { child: 0 })
+        );
+    }
+
+    #[test]
+    fn too_large() {
+        let mut layout = side_by_side();
+        layout.width = u32::MAX;
+        assert_eq!(
+            layout.validate(),
+            Err(CombinedDisplayLayoutError::DesktopTooLarge)
+        );
+
+        let mut layout = side_by_side();
+        layout.children[1].target_mode.height = u32::MAX;
+        assert_eq!(
+            layout.validate(),
+            Err(CombinedDisplayLayoutError::TargetModeTooLarge { child: 1 })
+        );
+    }
+}
@@ -1,11 +1,15 @@
 use std::{
     borrow::Cow,
     ffi::{c_char, CStr},
+    fmt,
     mem::MaybeUninit,
     sync::Arc,
 };
 
 use crate::{
+    adapter_info::{AdapterInfo, AdapterPropertiesFlags, SupportedFunctions},
+    display::DisplayOutput,
+    driver_version::DriverVersion,
     error::{Error, Result},
     ffi::{
         ctl_3d_feature_getset_t, ctl_3d_feature_t, ctl_adapter_bdf_t, ctl_data_type_t,
@@ -19,6 +23,7 @@ use crate::{
 /// Specifies the scope in which to query for driver settings.
 /// Note that IGCL will not fall back to a wider scope when settings haven't been specified for the current one.
 /// This is solved by manually falling back to wider scopes in the query functions themselves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DriverSettingScope<'a> {
     /// Read the global settings.
     Global,
@@ -52,9 +57,12 @@ impl DriverSettingScope<'_> {
 // Despite passing a string length of `0` newer Intel drivers
 // starting at at least version `32.0.101.8531` require the string
 // *pointer* to be NULL rather than the dangling always-non-NULL
-// pointer Rust uses, or SEGFAULT otherwise.
-fn string_ptr_or_null(s: &str) -> *mut c_char {
-    if s.is_empty() {
+// pointer Rust uses, or SEGFAULT otherwise. Older drivers keep
+// receiving the (unread) dangling pointer they were validated with.
+const NULL_EMPTY_STRING_DRIVER_VERSION: DriverVersion = DriverVersion::new(32, 0, 101, 8531);
+
+fn string_ptr_or_null(s: &str, driver_version: DriverVersion) -> *mut c_char {
+    if s.is_empty() && driver_version >= NULL_EMPTY_STRING_DRIVER_VERSION {
         std::ptr::null_mut()
     } else {
         // IGCL API projection is wrong, they take mutable pointers
@@ -120,6 +128,39 @@ impl DeviceAdapter {
         self.adapter_properties.device_type
     }
 
+    pub fn driver_version(&self) -> DriverVersion {
+        self.adapter_properties.driver_version.into()
+    }
+
+    /// Retrieve an owned snapshot of the adapter properties.
+    pub fn info(&self) -> AdapterInfo {
+        let properties = &self.adapter_properties;
+
+        AdapterInfo {
+            name: self.name().to_string_lossy().into_owned(),
+            device_id: self.device_id.clone(),
+            device_type: self.device_type().into(),
+            supported_functions: SupportedFunctions::from_bits_retain(
+                properties.supported_subfunction_flags,
+            ),
+            adapter_flags: AdapterPropertiesFlags::from_bits_retain(
+                properties.graphics_adapter_properties,
+            ),
+            driver_version: self.driver_version(),
+            pci_vendor_id: self.pci_vendor_id(),
+            pci_device_id: self.pci_device_id(),
+            pci_subsys_vendor_id: self.pci_subsys_vendor_id(),
+            pci_subsys_id: self.pci_subsys_id(),
+            rev_id: properties.rev_id,
+            bus_device_function: self.bus_device_function(),
+            num_eus_per_sub_slice: properties.num_eus_per_sub_slice,
+            num_sub_slices_per_slice: properties.num_sub_slices_per_slice,
+            num_slices: properties.num_slices,
+            num_xe_cores: properties.num_xe_cores,
+            frequency: properties.Frequency,
+        }
+    }
+
     /// Attempt to query the endurance gaming driver setting for the specified scope.
     /// Falls back to a higher scope if the setting could not be found in the current one.
     #[doc(alias = "CTL_3D_FEATURE_ENDURANCE_GAMING")]
@@ -138,7 +179,7 @@ impl DeviceAdapter {
                 Size: std::mem::size_of::<ctl_3d_feature_getset_t>() as u32,
                 Version: 0,
                 FeatureType: ctl_3d_feature_t::CTL_3D_FEATURE_ENDURANCE_GAMING,
-                ApplicationName: string_ptr_or_null(&current_app),
+                ApplicationName: string_ptr_or_null(&current_app, self.driver_version()),
                 ApplicationNameLength: current_app.len() as i8,
                 bSet: false,
                 ValueType: ctl_property_value_type_t::CTL_PROPERTY_VALUE_TYPE_CUSTOM,
@@ -178,7 +219,7 @@ impl DeviceAdapter {
                 Size: std::mem::size_of::<ctl_3d_feature_getset_t>() as u32,
                 Version: 0,
                 FeatureType: ctl_3d_feature_t::CTL_3D_FEATURE_FRAME_LIMIT,
-                ApplicationName: string_ptr_or_null(&current_app),
+                ApplicationName: string_ptr_or_null(&current_app, self.driver_version()),
                 ApplicationNameLength: current_app.len() as i8,
                 bSet: false,
                 ValueType: ctl_property_value_type_t::CTL_PROPERTY_VALUE_TYPE_INT32,
@@ -222,7 +263,7 @@ impl DeviceAdapter {
                 Size: std::mem::size_of::<ctl_3d_feature_getset_t>() as u32,
                 Version: 0,
                 FeatureType: ctl_3d_feature_t::CTL_3D_FEATURE_GAMING_FLIP_MODES,
-                ApplicationName: string_ptr_or_null(&current_app),
+                ApplicationName: string_ptr_or_null(&current_app, self.driver_version()),
                 ApplicationNameLength: current_app.len() as i8,
                 bSet: false,
                 ValueType: ctl_property_value_type_t::CTL_PROPERTY_VALUE_TYPE_ENUM,
@@ -280,6 +321,45 @@ impl DeviceAdapter {
             .collect())
     }
 
+    /// Enumerate the display outputs of this adapter, including ones without an attached display.
+    /// Outputs for which the driver reports [`CTL_RESULT_ERROR_DISPLAY_NOT_ATTACHED`] while
+    /// querying their properties are skipped; any other error is returned.
+    ///
+    /// [`CTL_RESULT_ERROR_DISPLAY_NOT_ATTACHED`]: ctl_result_t::CTL_RESULT_ERROR_DISPLAY_NOT_ATTACHED
+    #[doc(alias = "ctlEnumerateDisplayOutputs")]
+    pub fn display_outputs(&self) -> Result<Vec<DisplayOutput>> {
+        let mut num_display_outputs = 0u32;
+        Error::from_result(unsafe {
+            self.control_lib.ctlEnumerateDisplayOutputs(
+                self.device_adapter_handle,
+                &mut num_display_outputs,
+                std::ptr::null_mut(),
+            )
+        })?;
+
+        let mut display_outputs = Vec::with_capacity(num_display_outputs as usize);
+
+        Error::from_result(unsafe {
+            self.control_lib.ctlEnumerateDisplayOutputs(
+                self.device_adapter_handle,
+                &mut num_display_outputs,
+                display_outputs.as_mut_ptr(),
+            )
+        })?;
+
+        unsafe { display_outputs.set_len(num_display_outputs as usize) };
+
+        display_outputs
+            .into_iter()
+            .filter_map(
+                |handle| match DisplayOutput::new(handle, self.control_lib.clone()) {
+                    Err(Error(ctl_result_t::CTL_RESULT_ERROR_DISPLAY_NOT_ATTACHED)) => None,
+                    output => Some(output),
+                },
+            )
+            .collect()
+    }
+
     #[doc(alias = "ctlPowerTelemetryGet")]
     pub fn power_telemetry(&self) -> Result<Telemetry> {
         let mut telemetry = ctl_power_telemetry_t {
@@ -316,7 +396,8 @@ impl DeviceAdapter {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Value {
     I8(i8),
     U8(u8),
@@ -330,7 +411,165 @@ pub enum Value {
     F64(f64),
 }
 
-#[derive(Debug)]
+impl Value {
+    /// Convert the value to an [`f64`], which may lose precision for large 64-bit integers.
+    pub fn as_f64(&self) -> f64 {
+        match *self {
+            Self::I8(v) => v.into(),
+            Self::U8(v) => v.into(),
+            Self::I16(v) => v.into(),
+            Self::U16(v) => v.into(),
+            Self::I32(v) => v.into(),
+            Self::U32(v) => v.into(),
+            Self::I64(v) => v as f64,
+            Self::U64(v) => v as f64,
+            Self::F32(v) => v.into(),
+            Self::F64(v) => v,
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::I8(v) => fmt::Display::fmt(v, f),
+            Self::U8(v) => fmt::Display::fmt(v, f),
+            Self::I16(v) => fmt::Display::fmt(v, f),
+            Self::U16(v) => fmt::Display::fmt(v, f),
+            Self::I32(v) => fmt::Display::fmt(v, f),
+            Self::U32(v) => fmt::Display::fmt(v, f),
+            Self::I64(v) => fmt::Display::fmt(v, f),
+            Self::U64(v) => fmt::Display::fmt(v, f),
+            Self::F32(v) => fmt_float(f64::from(*v), f),
+            Self::F64(v) => fmt_float(*v, f),
+        }
+    }
+}
+
+/// Format telemetry floats with at most three decimals, as the sensors are far less precise
+/// than their data type. An explicit precision, e.g. `{:.1}`, is honored instead.
+fn fmt_float(v: f64, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if f.precision().is_some() {
+        return fmt::Display::fmt(&v, f);
+    }
+
+    let s = format!("{v:.3}");
+    let s = if s.contains('.') {
+        s.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        &s
+    };
+    f.pad(if s == "-0" { "0" } else { s })
+}
+
+/// The unit of a [`Unit`], without its value.
+///
+/// Also contains units that IGCL never reports but that values can be converted to
+/// with [`TelemetryItem::value_in()`], such as [`UnitKind::FrequencyGhz`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum UnitKind {
+    FrequencyMhz,
+    FrequencyGhz,
+    OperationsGts,
+    OperationsMts,
+    VoltageVolts,
+    VoltageMillivolts,
+    PowerWatts,
+    PowerMilliwatts,
+    TemperatureCelsius,
+    EnergyJoules,
+    TimeSeconds,
+    MemoryBytes,
+    AngularSpeedRpm,
+    Percent,
+    MemSpeedGbps,
+    BandwidthMbps,
+}
+
+/// Physical quantity measured by a [`UnitKind`]; units can only be converted within one.
+#[derive(PartialEq)]
+enum Quantity {
+    Frequency,
+    TransferRate,
+    Voltage,
+    Power,
+    Temperature,
+    Energy,
+    Time,
+    Memory,
+    AngularSpeed,
+    Percentage,
+    Bandwidth,
+}
+
+impl UnitKind {
+    /// The quantity this unit measures, and the factor to convert a value in this unit
+    /// to the smallest unit of that quantity.
+    fn quantity(self) -> (Quantity, f64) {
+        match self {
+            Self::FrequencyMhz => (Quantity::Frequency, 1.0),
+            Self::FrequencyGhz => (Quantity::Frequency, 1_000.0),
+            Self::OperationsMts => (Quantity::TransferRate, 1.0),
+            Self::OperationsGts => (Quantity::TransferRate, 1_000.0),
+            Self::VoltageMillivolts => (Quantity::Voltage, 1.0),
+            Self::VoltageVolts => (Quantity::Voltage, 1_000.0),
+            Self::PowerMilliwatts => (Quantity::Power, 1.0),
+            Self::PowerWatts => (Quantity::Power, 1_000.0),
+            Self::TemperatureCelsius => (Quantity::Temperature, 1.0),
+            Self::EnergyJoules => (Quantity::Energy, 1.0),
+            Self::TimeSeconds => (Quantity::Time, 1.0),
+            Self::MemoryBytes => (Quantity::Memory, 1.0),
+            Self::AngularSpeedRpm => (Quantity::AngularSpeed, 1.0),
+            Self::Percent => (Quantity::Percentage, 1.0),
+            Self::BandwidthMbps => (Quantity::Bandwidth, 1.0),
+            Self::MemSpeedGbps => (Quantity::Bandwidth, 1_000.0),
+        }
+    }
+
+    /// Whether values in this unit can be converted to `other`.
+    pub fn is_compatible_with(self, other: Self) -> bool {
+        self.quantity().0 == other.quantity().0
+    }
+
+    /// Convert `value` from this unit to `target`, or [`None`] if the units are not compatible.
+    pub fn convert(self, value: f64, target: Self) -> Option<f64> {
+        let (quantity, from_scale) = self.quantity();
+        let (target_quantity, to_scale) = target.quantity();
+        (quantity == target_quantity).then(|| value * from_scale / to_scale)
+    }
+
+    /// The symbol used when displaying a value in this unit.
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Self::FrequencyMhz => "MHz",
+            Self::FrequencyGhz => "GHz",
+            Self::OperationsGts => "GT/s",
+            Self::OperationsMts => "MT/s",
+            Self::VoltageVolts => "V",
+            Self::VoltageMillivolts => "mV",
+            Self::PowerWatts => "W",
+            Self::PowerMilliwatts => "mW",
+            Self::TemperatureCelsius => "°C",
+            Self::EnergyJoules => "J",
+            Self::TimeSeconds => "s",
+            Self::MemoryBytes => "B",
+            Self::AngularSpeedRpm => "RPM",
+            Self::Percent => "%",
+            Self::MemSpeedGbps => "GB/s",
+            Self::BandwidthMbps => "MB/s",
+        }
+    }
+}
+
+impl fmt::Display for UnitKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.symbol())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Unit {
     FrequencyMhz(Value),
     OperationsGts(Value),
@@ -349,10 +588,80 @@ pub enum Unit {
     BandwidthMbps(Value),
 }
 
+impl Unit {
+    /// The unit of this value, regardless of its data type.
+    pub fn kind(&self) -> UnitKind {
+        match self {
+            Self::FrequencyMhz(_) => UnitKind::FrequencyMhz,
+            Self::OperationsGts(_) => UnitKind::OperationsGts,
+            Self::OperationsMts(_) => UnitKind::OperationsMts,
+            Self::VoltageVolts(_) => UnitKind::VoltageVolts,
+            Self::PowerWatts(_) => UnitKind::PowerWatts,
+            Self::TemperatureCelsius(_) => UnitKind::TemperatureCelsius,
+            Self::EnergyJoules(_) => UnitKind::EnergyJoules,
+            Self::TimeSeconds(_) => UnitKind::TimeSeconds,
+            Self::MemoryBytes(_) => UnitKind::MemoryBytes,
+            Self::AngularSpeedRpm(_) => UnitKind::AngularSpeedRpm,
+            Self::PowerMilliwatts(_) => UnitKind::PowerMilliwatts,
+            Self::Percent(_) => UnitKind::Percent,
+            Self::MemSpeedGbps(_) => UnitKind::MemSpeedGbps,
+            Self::VoltageMillivolts(_) => UnitKind::VoltageMillivolts,
+            Self::BandwidthMbps(_) => UnitKind::BandwidthMbps,
+        }
+    }
+
+    /// The raw value, in the units described by this variant.
+    pub fn value(&self) -> &Value {
+        match self {
+            Self::FrequencyMhz(v)
+            | Self::OperationsGts(v)
+            | Self::OperationsMts(v)
+            | Self::VoltageVolts(v)
+            | Self::PowerWatts(v)
+            | Self::TemperatureCelsius(v)
+            | Self::EnergyJoules(v)
+            | Self::TimeSeconds(v)
+            | Self::MemoryBytes(v)
+            | Self::AngularSpeedRpm(v)
+            | Self::PowerMilliwatts(v)
+            | Self::Percent(v)
+            | Self::MemSpeedGbps(v)
+            | Self::VoltageMillivolts(v)
+            | Self::BandwidthMbps(v) => v,
+        }
+    }
+}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.value(), f)?;
+        write!(f, " {}", self.kind())
+    }
+}
+
 #[doc(alias = "ctl_oc_telemetry_item_t")]
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TelemetryItem(pub Option<Unit>);
 
+impl TelemetryItem {
+    /// Retrieve the value converted to `kind`, or [`None`] if this item is unsupported or
+    /// its unit cannot be converted to `kind`.
+    pub fn value_in(&self, kind: UnitKind) -> Option<f64> {
+        let unit = self.0.as_ref()?;
+        unit.kind().convert(unit.value().as_f64(), kind)
+    }
+}
+
+impl fmt::Display for TelemetryItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            Some(unit) => fmt::Display::fmt(unit, f),
+            None => f.write_str("unsupported"),
+        }
+    }
+}
+
 impl From<ctl_oc_telemetry_item_t> for TelemetryItem {
     fn from(item: ctl_oc_telemetry_item_t) -> Self {
         TelemetryItem(if item.bSupported {
@@ -397,7 +706,8 @@ impl From<ctl_oc_telemetry_item_t> for TelemetryItem {
 }
 
 #[doc(alias = "ctl_power_telemetry_t")]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Telemetry {
     pub time_stamp: TelemetryItem,
     pub gpu_energy_counter: TelemetryItem,
@@ -429,3 +739,93 @@ pub struct Telemetry {
     // pub psu: [ctl_psu_info_t; 5usize],
     // pub fanSpeed: [ctl_oc_telemetry_item_t; 5usize],
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_string_pointer() {
+        let old = DriverVersion::new(31, 0, 101, 4502);
+        assert!(!string_ptr_or_null("", old).is_null());
+        assert!(string_ptr_or_null("", NULL_EMPTY_STRING_DRIVER_VERSION).is_null());
+        assert!(string_ptr_or_null("", DriverVersion::new(32, 0, 101, 9000)).is_null());
+
+        let name = "game.exe";
+        assert_eq!(
+            string_ptr_or_null(name, NULL_EMPTY_STRING_DRIVER_VERSION),
+            name.as_ptr().cast::<c_char>().cast_mut()
+        );
+    }
+
+    #[test]
+    fn unit_conversion() {
+        assert_eq!(
+            UnitKind::FrequencyMhz.convert(1500.0, UnitKind::FrequencyGhz),
+            Some(1.5)
+        );
+        assert_eq!(
+            UnitKind::VoltageVolts.convert(1.2, UnitKind::VoltageMillivolts),
+            Some(1200.0)
+        );
+        assert_eq!(
+            UnitKind::PowerMilliwatts.convert(2500.0, UnitKind::PowerWatts),
+            Some(2.5)
+        );
+        assert_eq!(
+            UnitKind::MemSpeedGbps.convert(2.0, UnitKind::BandwidthMbps),
+            Some(2000.0)
+        );
+        assert_eq!(
+            UnitKind::TemperatureCelsius.convert(40.0, UnitKind::TemperatureCelsius),
+            Some(40.0)
+        );
+    }
+
+    #[test]
+    fn incompatible_units() {
+        assert!(!UnitKind::PowerWatts.is_compatible_with(UnitKind::EnergyJoules));
+        assert!(UnitKind::OperationsGts.is_compatible_with(UnitKind::OperationsMts));
+        assert_eq!(
+            UnitKind::PowerWatts.convert(1.0, UnitKind::EnergyJoules),
+            None
+        );
+        assert_eq!(
+            UnitKind::FrequencyMhz.convert(1.0, UnitKind::OperationsMts),
+            None
+        );
+    }
+
+    #[test]
+    fn item_value_in() {
+        let item = TelemetryItem(Some(Unit::VoltageMillivolts(Value::U32(850))));
+        assert_eq!(item.value_in(UnitKind::VoltageVolts), Some(0.85));
+        assert_eq!(item.value_in(UnitKind::PowerWatts), None);
+        assert_eq!(TelemetryItem(None).value_in(UnitKind::VoltageVolts), None);
+    }
+
+    #[test]
+    fn as_f64() {
+        assert_eq!(Value::I8(-3).as_f64(), -3.0);
+        assert_eq!(Value::U64(1 << 40).as_f64(), (1u64 << 40) as f64);
+        assert_eq!(Value::F32(0.5).as_f64(), 0.5);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Unit::VoltageVolts(Value::F32(0.1)).to_string(), "0.1 V");
+        assert_eq!(
+            Unit::PowerWatts(Value::F64(12.345678)).to_string(),
+            "12.346 W"
+        );
+        assert_eq!(
+            Unit::TemperatureCelsius(Value::F64(45.0)).to_string(),
+            "45 °C"
+        );
+        assert_eq!(Unit::Percent(Value::F64(-0.0001)).to_string(), "0 %");
+        assert_eq!(Unit::FrequencyMhz(Value::U32(2400)).to_string(), "2400 MHz");
+        assert_eq!(format!("{:.1}", Value::F64(1.25)), "1.2");
+        assert_eq!(Value::F64(f64::NAN).to_string(), "NaN");
+        assert_eq!(TelemetryItem(None).to_string(), "unsupported");
+    }
+}
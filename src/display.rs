@@ -0,0 +1,470 @@
+use std::sync::Arc;
+
+use crate::{
+    error::{Error, Result},
+    ffi::{
+        ctl_adapter_display_encoder_properties_t, ctl_attached_display_mux_type_t,
+        ctl_display_config_flag_t, ctl_display_output_handle_t, ctl_display_output_types_t,
+        ctl_display_properties_t, ctl_display_timing_t, ctl_encoder_config_flag_t,
+        ctl_intel_display_feature_flag_t, ctl_output_bpc_flag_t, ctl_signal_standard_type_t,
+        ctl_std_display_feature_flag_t, ControlLib,
+    },
+};
+
+pub mod arc_sync;
+pub mod brightness;
+pub mod custom_mode;
+pub mod dce;
+pub mod edid;
+pub mod lace;
+pub mod pixel_pipeline;
+pub mod power_optimization;
+pub mod settings;
+pub mod sharpness;
+pub mod software_psr;
+pub mod vblank;
+pub mod wire_format;
+
+#[doc(alias = "ctl_display_output_types_t")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DisplayOutputType {
+    Invalid,
+    DisplayPort,
+    Hdmi,
+    Dvi,
+    Mipi,
+    Crt,
+    /// An output type not known to these bindings.
+    Unknown(i32),
+}
+
+impl From<ctl_display_output_types_t> for DisplayOutputType {
+    fn from(output_type: ctl_display_output_types_t) -> Self {
+        match output_type {
+            ctl_display_output_types_t::CTL_DISPLAY_OUTPUT_TYPES_INVALID => Self::Invalid,
+            ctl_display_output_types_t::CTL_DISPLAY_OUTPUT_TYPES_DISPLAYPORT => Self::DisplayPort,
+            ctl_display_output_types_t::CTL_DISPLAY_OUTPUT_TYPES_HDMI => Self::Hdmi,
+            ctl_display_output_types_t::CTL_DISPLAY_OUTPUT_TYPES_DVI => Self::Dvi,
+            ctl_display_output_types_t::CTL_DISPLAY_OUTPUT_TYPES_MIPI => Self::Mipi,
+            ctl_display_output_types_t::CTL_DISPLAY_OUTPUT_TYPES_CRT => Self::Crt,
+            ctl_display_output_types_t(x) => Self::Unknown(x),
+        }
+    }
+}
+
+#[doc(alias = "ctl_attached_display_mux_type_t")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AttachedDisplayMuxType {
+    /// Native DisplayPort or HDMI.
+    Native,
+    Thunderbolt,
+    TypeC,
+    Usb4,
+    /// A mux type not known to these bindings.
+    Unknown(i32),
+}
+
+impl From<ctl_attached_display_mux_type_t> for AttachedDisplayMuxType {
+    fn from(mux_type: ctl_attached_display_mux_type_t) -> Self {
+        match mux_type {
+            ctl_attached_display_mux_type_t::CTL_ATTACHED_DISPLAY_MUX_TYPE_NATIVE => Self::Native,
+            ctl_attached_display_mux_type_t::CTL_ATTACHED_DISPLAY_MUX_TYPE_THUNDERBOLT => {
+                Self::Thunderbolt
+            }
+            ctl_attached_display_mux_type_t::CTL_ATTACHED_DISPLAY_MUX_TYPE_TYPE_C => Self::TypeC,
+            ctl_attached_display_mux_type_t::CTL_ATTACHED_DISPLAY_MUX_TYPE_USB4 => Self::Usb4,
+            ctl_attached_display_mux_type_t(x) => Self::Unknown(x),
+        }
+    }
+}
+
+#[doc(alias = "ctl_signal_standard_type_t")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SignalStandard {
+    Unknown,
+    /// Custom added timing.
+    Custom,
+    Dmt,
+    Gtf,
+    Cvt,
+    Cta,
+    /// A signal standard not known to these bindings.
+    Other(i32),
+}
+
+impl From<ctl_signal_standard_type_t> for SignalStandard {
+    fn from(standard: ctl_signal_standard_type_t) -> Self {
+        match standard {
+            ctl_signal_standard_type_t::CTL_SIGNAL_STANDARD_TYPE_UNKNOWN => Self::Unknown,
+            ctl_signal_standard_type_t::CTL_SIGNAL_STANDARD_TYPE_CUSTOM => Self::Custom,
+            ctl_signal_standard_type_t::CTL_SIGNAL_STANDARD_TYPE_DMT => Self::Dmt,
+            ctl_signal_standard_type_t::CTL_SIGNAL_STANDARD_TYPE_GTF => Self::Gtf,
+            ctl_signal_standard_type_t::CTL_SIGNAL_STANDARD_TYPE_CVT => Self::Cvt,
+            ctl_signal_standard_type_t::CTL_SIGNAL_STANDARD_TYPE_CTA => Self::Cta,
+            ctl_signal_standard_type_t(x) => Self::Other(x),
+        }
+    }
+}
+
+bitflags::bitflags! {
+    #[doc(alias = "ctl_output_bpc_flags_t")]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct OutputBpcFlags: u32 {
+        const BPC_6 = ctl_output_bpc_flag_t::CTL_OUTPUT_BPC_FLAG_6BPC.0 as u32;
+        const BPC_8 = ctl_output_bpc_flag_t::CTL_OUTPUT_BPC_FLAG_8BPC.0 as u32;
+        const BPC_10 = ctl_output_bpc_flag_t::CTL_OUTPUT_BPC_FLAG_10BPC.0 as u32;
+        const BPC_12 = ctl_output_bpc_flag_t::CTL_OUTPUT_BPC_FLAG_12BPC.0 as u32;
+    }
+}
+
+bitflags::bitflags! {
+    #[doc(alias = "ctl_display_config_flags_t")]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct DisplayConfigFlags: u32 {
+        const DISPLAY_ACTIVE = ctl_display_config_flag_t::CTL_DISPLAY_CONFIG_FLAG_DISPLAY_ACTIVE.0 as u32;
+        /// A dongle, display or hub is attached to the encoder.
+        const DISPLAY_ATTACHED = ctl_display_config_flag_t::CTL_DISPLAY_CONFIG_FLAG_DISPLAY_ATTACHED.0 as u32;
+        /// A dongle, hub or onboard protocol converter is attached to the encoder.
+        const DONGLE_CONNECTED_TO_ENCODER = ctl_display_config_flag_t::CTL_DISPLAY_CONFIG_FLAG_IS_DONGLE_CONNECTED_TO_ENCODER.0 as u32;
+        const DITHERING_ENABLED = ctl_display_config_flag_t::CTL_DISPLAY_CONFIG_FLAG_DITHERING_ENABLED.0 as u32;
+    }
+}
+
+bitflags::bitflags! {
+    #[doc(alias = "ctl_encoder_config_flags_t")]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct EncoderConfigFlags: u32 {
+        /// Internal connection, e.g. an eDP or MIPI laptop panel.
+        const INTERNAL_DISPLAY = ctl_encoder_config_flag_t::CTL_ENCODER_CONFIG_FLAG_INTERNAL_DISPLAY.0 as u32;
+        /// VESA DisplayID based tiled display driven by multiple SST connections or MST streams.
+        const VESA_TILED_DISPLAY = ctl_encoder_config_flag_t::CTL_ENCODER_CONFIG_FLAG_VESA_TILED_DISPLAY.0 as u32;
+        const TYPEC_CAPABLE = ctl_encoder_config_flag_t::CTL_ENCODER_CONFIG_FLAG_TYPEC_CAPABLE.0 as u32;
+        /// Thunderbolt capable.
+        const TBT_CAPABLE = ctl_encoder_config_flag_t::CTL_ENCODER_CONFIG_FLAG_TBT_CAPABLE.0 as u32;
+        const DITHERING_SUPPORTED = ctl_encoder_config_flag_t::CTL_ENCODER_CONFIG_FLAG_DITHERING_SUPPORTED.0 as u32;
+        /// Software display to which hardware based features do not apply.
+        const VIRTUAL_DISPLAY = ctl_encoder_config_flag_t::CTL_ENCODER_CONFIG_FLAG_VIRTUAL_DISPLAY.0 as u32;
+        /// Display is hidden from the OS.
+        const HIDDEN_DISPLAY = ctl_encoder_config_flag_t::CTL_ENCODER_CONFIG_FLAG_HIDDEN_DISPLAY.0 as u32;
+        const COLLAGE_DISPLAY = ctl_encoder_config_flag_t::CTL_ENCODER_CONFIG_FLAG_COLLAGE_DISPLAY.0 as u32;
+        const SPLIT_DISPLAY = ctl_encoder_config_flag_t::CTL_ENCODER_CONFIG_FLAG_SPLIT_DISPLAY.0 as u32;
+        const COMPANION_DISPLAY = ctl_encoder_config_flag_t::CTL_ENCODER_CONFIG_FLAG_COMPANION_DISPLAY.0 as u32;
+        /// Collage display spanning multiple GPUs.
+        const MGPU_COLLAGE_DISPLAY = ctl_encoder_config_flag_t::CTL_ENCODER_CONFIG_FLAG_MGPU_COLLAGE_DISPLAY.0 as u32;
+    }
+}
+
+bitflags::bitflags! {
+    /// Industry standard display features.
+    #[doc(alias = "ctl_std_display_feature_flags_t")]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct StdDisplayFeatures: u32 {
+        const HDCP = ctl_std_display_feature_flag_t::CTL_STD_DISPLAY_FEATURE_FLAG_HDCP.0 as u32;
+        const HD_AUDIO = ctl_std_display_feature_flag_t::CTL_STD_DISPLAY_FEATURE_FLAG_HD_AUDIO.0 as u32;
+        /// VESA Panel Self Refresh.
+        const PSR = ctl_std_display_feature_flag_t::CTL_STD_DISPLAY_FEATURE_FLAG_PSR.0 as u32;
+        /// VESA Adaptive Sync or HDMI VRR.
+        const ADAPTIVE_SYNC_VRR = ctl_std_display_feature_flag_t::CTL_STD_DISPLAY_FEATURE_FLAG_ADAPTIVESYNC_VRR.0 as u32;
+        /// VESA Display Stream Compression.
+        const VESA_COMPRESSION = ctl_std_display_feature_flag_t::CTL_STD_DISPLAY_FEATURE_FLAG_VESA_COMPRESSION.0 as u32;
+        const HDR = ctl_std_display_feature_flag_t::CTL_STD_DISPLAY_FEATURE_FLAG_HDR.0 as u32;
+        const HDMI_QMS = ctl_std_display_feature_flag_t::CTL_STD_DISPLAY_FEATURE_FLAG_HDMI_QMS.0 as u32;
+        const HDR10_PLUS_CERTIFIED = ctl_std_display_feature_flag_t::CTL_STD_DISPLAY_FEATURE_FLAG_HDR10_PLUS_CERTIFIED.0 as u32;
+        const VESA_HDR_CERTIFIED = ctl_std_display_feature_flag_t::CTL_STD_DISPLAY_FEATURE_FLAG_VESA_HDR_CERTIFIED.0 as u32;
+    }
+}
+
+bitflags::bitflags! {
+    /// Advanced display features provided by Intel graphics adapters.
+    #[doc(alias = "ctl_intel_display_feature_flags_t")]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct IntelDisplayFeatures: u32 {
+        /// Display Power Saving Technology.
+        const DPST = ctl_intel_display_feature_flag_t::CTL_INTEL_DISPLAY_FEATURE_FLAG_DPST.0 as u32;
+        /// Local Adaptive Contrast Enhancement.
+        const LACE = ctl_intel_display_feature_flag_t::CTL_INTEL_DISPLAY_FEATURE_FLAG_LACE.0 as u32;
+        /// Display Refresh Rate Switching.
+        const DRRS = ctl_intel_display_feature_flag_t::CTL_INTEL_DISPLAY_FEATURE_FLAG_DRRS.0 as u32;
+        /// Intel Arc Sync certified adaptive sync display.
+        const ARC_ADAPTIVE_SYNC_CERTIFIED = ctl_intel_display_feature_flag_t::CTL_INTEL_DISPLAY_FEATURE_FLAG_ARC_ADAPTIVE_SYNC_CERTIFIED.0 as u32;
+    }
+}
+
+impl From<SignalStandard> for ctl_signal_standard_type_t {
+    fn from(standard: SignalStandard) -> Self {
+        match standard {
+            SignalStandard::Unknown => Self::CTL_SIGNAL_STANDARD_TYPE_UNKNOWN,
+            SignalStandard::Custom => Self::CTL_SIGNAL_STANDARD_TYPE_CUSTOM,
+            SignalStandard::Dmt => Self::CTL_SIGNAL_STANDARD_TYPE_DMT,
+            SignalStandard::Gtf => Self::CTL_SIGNAL_STANDARD_TYPE_GTF,
+            SignalStandard::Cvt => Self::CTL_SIGNAL_STANDARD_TYPE_CVT,
+            SignalStandard::Cta => Self::CTL_SIGNAL_STANDARD_TYPE_CTA,
+            SignalStandard::Other(x) => Self(x),
+        }
+    }
+}
+
+/// Timing applied on a display.
+#[doc(alias = "ctl_display_timing_t")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DisplayTiming {
+    /// Pixel clock in Hz.
+    pub pixel_clock: u64,
+    pub h_active: u32,
+    pub v_active: u32,
+    pub h_total: u32,
+    pub v_total: u32,
+    pub h_blank: u32,
+    pub v_blank: u32,
+    pub h_sync: u32,
+    pub v_sync: u32,
+    /// Refresh rate in Hz.
+    pub refresh_rate: f32,
+    pub signal_standard: SignalStandard,
+    /// VIC ID for CTA timings.
+    pub vic_id: u8,
+}
+
+impl From<ctl_display_timing_t> for DisplayTiming {
+    fn from(timing: ctl_display_timing_t) -> Self {
+        Self {
+            pixel_clock: timing.PixelClock,
+            h_active: timing.HActive,
+            v_active: timing.VActive,
+            h_total: timing.HTotal,
+            v_total: timing.VTotal,
+            h_blank: timing.HBlank,
+            v_blank: timing.VBlank,
+            h_sync: timing.HSync,
+            v_sync: timing.VSync,
+            refresh_rate: timing.RefreshRate,
+            signal_standard: timing.SignalStandard.into(),
+            vic_id: timing.VicId,
+        }
+    }
+}
+
+impl From<DisplayTiming> for ctl_display_timing_t {
+    fn from(timing: DisplayTiming) -> Self {
+        Self {
+            Size: std::mem::size_of::<ctl_display_timing_t>() as u32,
+            Version: 0,
+            PixelClock: timing.pixel_clock,
+            HActive: timing.h_active,
+            VActive: timing.v_active,
+            HTotal: timing.h_total,
+            VTotal: timing.v_total,
+            HBlank: timing.h_blank,
+            VBlank: timing.v_blank,
+            HSync: timing.h_sync,
+            VSync: timing.v_sync,
+            RefreshRate: timing.refresh_rate,
+            SignalStandard: timing.signal_standard.into(),
+            VicId: timing.vic_id,
+        }
+    }
+}
+
+/// Properties of the display currently attached to a [`DisplayOutput`].
+#[doc(alias = "ctl_display_properties_t")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DisplayProperties {
+    /// Output type from the display hardware point of view, which is
+    /// [`DisplayOutputType::DisplayPort`] when a protocol converter is involved.
+    pub output_type: DisplayOutputType,
+    pub mux_type: AttachedDisplayMuxType,
+    /// Output type of the protocol converter, or [`DisplayOutputType::Invalid`]
+    /// if there is none.
+    pub protocol_converter_output: DisplayOutputType,
+    /// Maximum supported output bits per color, independent of RGB or YCbCr output.
+    pub supported_bpc: OutputBpcFlags,
+    pub config_flags: DisplayConfigFlags,
+    pub features_supported: StdDisplayFeatures,
+    pub features_enabled: StdDisplayFeatures,
+    pub intel_features_supported: IntelDisplayFeatures,
+    pub intel_features_enabled: IntelDisplayFeatures,
+    /// Timing currently applied on the display.
+    pub timing: DisplayTiming,
+}
+
+impl From<ctl_display_properties_t> for DisplayProperties {
+    fn from(properties: ctl_display_properties_t) -> Self {
+        Self {
+            output_type: properties.Type.into(),
+            mux_type: properties.AttachedDisplayMuxType.into(),
+            protocol_converter_output: properties.ProtocolConverterOutput.into(),
+            supported_bpc: OutputBpcFlags::from_bits_retain(properties.SupportedOutputBPCFlags),
+            config_flags: DisplayConfigFlags::from_bits_retain(properties.DisplayConfigFlags),
+            features_supported: StdDisplayFeatures::from_bits_retain(
+                properties.FeatureSupportedFlags,
+            ),
+            features_enabled: StdDisplayFeatures::from_bits_retain(properties.FeatureEnabledFlags),
+            intel_features_supported: IntelDisplayFeatures::from_bits_retain(
+                properties.AdvancedFeatureSupportedFlags,
+            ),
+            intel_features_enabled: IntelDisplayFeatures::from_bits_retain(
+                properties.AdvancedFeatureEnabledFlags,
+            ),
+            timing: properties.Display_Timing_Info.into(),
+        }
+    }
+}
+
+impl DisplayProperties {
+    pub fn is_attached(&self) -> bool {
+        self.config_flags
+            .contains(DisplayConfigFlags::DISPLAY_ATTACHED)
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.config_flags
+            .contains(DisplayConfigFlags::DISPLAY_ACTIVE)
+    }
+}
+
+/// Properties of the display encoder driving a [`DisplayOutput`], regardless of the
+/// attached display.
+#[doc(alias = "ctl_adapter_display_encoder_properties_t")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EncoderProperties {
+    pub output_type: DisplayOutputType,
+    pub is_on_board_protocol_converter_output_present: bool,
+    pub supported_bpc: OutputBpcFlags,
+    pub config_flags: EncoderConfigFlags,
+    pub features_supported: StdDisplayFeatures,
+    pub intel_features_supported: IntelDisplayFeatures,
+}
+
+impl From<ctl_adapter_display_encoder_properties_t> for EncoderProperties {
+    fn from(properties: ctl_adapter_display_encoder_properties_t) -> Self {
+        Self {
+            output_type: properties.Type.into(),
+            is_on_board_protocol_converter_output_present: properties
+                .IsOnBoardProtocolConverterOutputPresent,
+            supported_bpc: OutputBpcFlags::from_bits_retain(properties.SupportedOutputBPCFlags),
+            config_flags: EncoderConfigFlags::from_bits_retain(properties.EncoderConfigFlags),
+            features_supported: StdDisplayFeatures::from_bits_retain(
+                properties.FeatureSupportedFlags,
+            ),
+            intel_features_supported: IntelDisplayFeatures::from_bits_retain(
+                properties.AdvancedFeatureSupportedFlags,
+            ),
+        }
+    }
+}
+
+impl EncoderProperties {
+    pub fn is_internal_display(&self) -> bool {
+        self.config_flags
+            .contains(EncoderConfigFlags::INTERNAL_DISPLAY)
+    }
+
+    pub fn supports_hdcp(&self) -> bool {
+        self.features_supported.contains(StdDisplayFeatures::HDCP)
+    }
+}
+
+#[doc(alias = "ctl_display_output_handle_t")]
+pub struct DisplayOutput {
+    pub(crate) display_output_handle: ctl_display_output_handle_t,
+    pub(crate) properties: DisplayProperties,
+    pub(crate) control_lib: Arc<ControlLib>,
+}
+
+impl DisplayOutput {
+    pub(crate) fn new(
+        display_output_handle: ctl_display_output_handle_t,
+        control_lib: Arc<ControlLib>,
+    ) -> Result<Self> {
+        let properties = query_properties(&control_lib, display_output_handle)?;
+
+        Ok(Self {
+            display_output_handle,
+            properties,
+            control_lib,
+        })
+    }
+
+    pub fn display_output_handle(&self) -> ctl_display_output_handle_t {
+        self.display_output_handle
+    }
+
+    /// Properties of the display as they were when this output was enumerated or last
+    /// refreshed with [`Self::refresh_properties()`].
+    pub fn properties(&self) -> &DisplayProperties {
+        &self.properties
+    }
+
+    /// Re-query the display properties, e.g. after a monitor was plugged in or a mode change.
+    #[doc(alias = "ctlGetDisplayProperties")]
+    pub fn refresh_properties(&mut self) -> Result<&DisplayProperties> {
+        self.properties = query_properties(&self.control_lib, self.display_output_handle)?;
+        Ok(&self.properties)
+    }
+
+    pub fn output_type(&self) -> DisplayOutputType {
+        self.properties.output_type
+    }
+
+    pub fn is_attached(&self) -> bool {
+        self.properties.is_attached()
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.properties.is_active()
+    }
+
+    pub fn supported_bpc(&self) -> OutputBpcFlags {
+        self.properties.supported_bpc
+    }
+
+    pub fn timing(&self) -> &DisplayTiming {
+        &self.properties.timing
+    }
+
+    #[doc(alias = "ctlGetAdaperDisplayEncoderProperties")]
+    pub fn encoder_properties(&self) -> Result<EncoderProperties> {
+        let mut properties = ctl_adapter_display_encoder_properties_t {
+            Size: std::mem::size_of::<ctl_adapter_display_encoder_properties_t>() as u32,
+            Version: 0,
+            ..Default::default()
+        };
+
+        Error::from_result(unsafe {
+            self.control_lib
+                .ctlGetAdaperDisplayEncoderProperties(self.display_output_handle, &mut properties)
+        })?;
+
+        Ok(properties.into())
+    }
+}
+
+#[doc(alias = "ctlGetDisplayProperties")]
+fn query_properties(
+    control_lib: &ControlLib,
+    display_output_handle: ctl_display_output_handle_t,
+) -> Result<DisplayProperties> {
+    let mut properties = ctl_display_properties_t {
+        Size: std::mem::size_of::<ctl_display_properties_t>() as u32,
+        Version: 0,
+        ..Default::default()
+    };
+    properties.Display_Timing_Info.Size = std::mem::size_of::<ctl_display_timing_t>() as u32;
+
+    Error::from_result(unsafe {
+        control_lib.ctlGetDisplayProperties(display_output_handle, &mut properties)
+    })?;
+
+    Ok(properties.into())
+}
@@ -0,0 +1,388 @@
+use std::fmt;
+
+use crate::{
+    display::DisplayOutput,
+    error::{Error, Result},
+    ffi::{
+        ctl_intel_arc_sync_monitor_params_t, ctl_intel_arc_sync_profile_params_t,
+        ctl_intel_arc_sync_profile_t,
+    },
+};
+
+/// Intel Arc Sync (variable refresh rate) capabilities of the attached monitor.
+#[doc(alias = "ctl_intel_arc_sync_monitor_params_t")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ArcSyncMonitorInfo {
+    pub supported: bool,
+    pub min_refresh_rate: f32,
+    pub max_refresh_rate: f32,
+    /// Maximum frame time increase in µs, from the DisplayID 2.1 adaptive sync block.
+    pub max_frame_time_increase: u32,
+    /// Maximum frame time decrease in µs, from the DisplayID 2.1 adaptive sync block.
+    pub max_frame_time_decrease: u32,
+}
+
+impl From<ctl_intel_arc_sync_monitor_params_t> for ArcSyncMonitorInfo {
+    fn from(params: ctl_intel_arc_sync_monitor_params_t) -> Self {
+        Self {
+            supported: params.IsIntelArcSyncSupported,
+            min_refresh_rate: params.MinimumRefreshRateInHz,
+            max_refresh_rate: params.MaximumRefreshRateInHz,
+            max_frame_time_increase: params.MaxFrameTimeIncreaseInUs,
+            max_frame_time_decrease: params.MaxFrameTimeDecreaseInUs,
+        }
+    }
+}
+
+#[doc(alias = "ctl_intel_arc_sync_profile_t")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ArcSyncProfile {
+    Invalid,
+    /// Selects a profile based on the monitor, falling back to [`Self::Compatible`].
+    Recommended,
+    /// Unconstrained, the full refresh rate range of the monitor is used.
+    Excellent,
+    /// Minor range constraints, which can reduce flicker on some monitors.
+    Good,
+    /// Significant range constraints, reducing flicker at the cost of judder.
+    Compatible,
+    /// Variable refresh rate is disabled for the monitor.
+    Off,
+    /// Constraints specified by the monitor through VESA, falling back to
+    /// [`Self::Compatible`].
+    Vesa,
+    /// User-defined constraints.
+    Custom,
+    /// A profile not known to these bindings.
+    Unknown(i32),
+}
+
+impl From<ctl_intel_arc_sync_profile_t> for ArcSyncProfile {
+    fn from(profile: ctl_intel_arc_sync_profile_t) -> Self {
+        match profile {
+            ctl_intel_arc_sync_profile_t::CTL_INTEL_ARC_SYNC_PROFILE_INVALID => Self::Invalid,
+            ctl_intel_arc_sync_profile_t::CTL_INTEL_ARC_SYNC_PROFILE_RECOMMENDED => {
+                Self::Recommended
+            }
+            ctl_intel_arc_sync_profile_t::CTL_INTEL_ARC_SYNC_PROFILE_EXCELLENT => Self::Excellent,
+            ctl_intel_arc_sync_profile_t::CTL_INTEL_ARC_SYNC_PROFILE_GOOD => Self::Good,
+            ctl_intel_arc_sync_profile_t::CTL_INTEL_ARC_SYNC_PROFILE_COMPATIBLE => Self::Compatible,
+            ctl_intel_arc_sync_profile_t::CTL_INTEL_ARC_SYNC_PROFILE_OFF => Self::Off,
+            ctl_intel_arc_sync_profile_t::CTL_INTEL_ARC_SYNC_PROFILE_VESA => Self::Vesa,
+            ctl_intel_arc_sync_profile_t::CTL_INTEL_ARC_SYNC_PROFILE_CUSTOM => Self::Custom,
+            ctl_intel_arc_sync_profile_t(x) => Self::Unknown(x),
+        }
+    }
+}
+
+impl From<ArcSyncProfile> for ctl_intel_arc_sync_profile_t {
+    fn from(profile: ArcSyncProfile) -> Self {
+        match profile {
+            ArcSyncProfile::Invalid => Self::CTL_INTEL_ARC_SYNC_PROFILE_INVALID,
+            ArcSyncProfile::Recommended => Self::CTL_INTEL_ARC_SYNC_PROFILE_RECOMMENDED,
+            ArcSyncProfile::Excellent => Self::CTL_INTEL_ARC_SYNC_PROFILE_EXCELLENT,
+            ArcSyncProfile::Good => Self::CTL_INTEL_ARC_SYNC_PROFILE_GOOD,
+            ArcSyncProfile::Compatible => Self::CTL_INTEL_ARC_SYNC_PROFILE_COMPATIBLE,
+            ArcSyncProfile::Off => Self::CTL_INTEL_ARC_SYNC_PROFILE_OFF,
+            ArcSyncProfile::Vesa => Self::CTL_INTEL_ARC_SYNC_PROFILE_VESA,
+            ArcSyncProfile::Custom => Self::CTL_INTEL_ARC_SYNC_PROFILE_CUSTOM,
+            ArcSyncProfile::Unknown(x) => Self(x),
+        }
+    }
+}
+
+/// An Intel Arc Sync profile and the constraints the driver applies with it.
+#[doc(alias = "ctl_intel_arc_sync_profile_params_t")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ArcSyncProfileParams {
+    pub profile: ArcSyncProfile,
+    pub min_refresh_rate: f32,
+    pub max_refresh_rate: f32,
+    /// Maximum frame time increase in µs.
+    pub max_frame_time_increase: u32,
+    /// Maximum frame time decrease in µs.
+    pub max_frame_time_decrease: u32,
+}
+
+impl ArcSyncProfileParams {
+    /// A predefined profile, whose constraints are chosen by the driver.
+    pub fn new(profile: ArcSyncProfile) -> Self {
+        Self {
+            profile,
+            min_refresh_rate: 0.0,
+            max_refresh_rate: 0.0,
+            max_frame_time_increase: 0,
+            max_frame_time_decrease: 0,
+        }
+    }
+
+    /// An [`ArcSyncProfile::Custom`] profile with the given constraints.
+    pub fn custom(
+        min_refresh_rate: f32,
+        max_refresh_rate: f32,
+        max_frame_time_increase: u32,
+        max_frame_time_decrease: u32,
+    ) -> Self {
+        Self {
+            profile: ArcSyncProfile::Custom,
+            min_refresh_rate,
+            max_refresh_rate,
+            max_frame_time_increase,
+            max_frame_time_decrease,
+        }
+    }
+
+    /// Check that the profile can be set on `monitor`: the monitor has to support Intel Arc
+    /// Sync, the profile has to be known, and the refresh rate range of an
+    /// [`ArcSyncProfile::Custom`] profile has to be non-empty and within the monitor range.
+    pub fn validate(&self, monitor: &ArcSyncMonitorInfo) -> Result<(), ArcSyncError> {
+        if matches!(
+            self.profile,
+            ArcSyncProfile::Invalid | ArcSyncProfile::Unknown(_)
+        ) {
+            return Err(ArcSyncError::InvalidProfile(self.profile));
+        }
+        if !monitor.supported {
+            return Err(ArcSyncError::Unsupported);
+        }
+
+        if self.profile == ArcSyncProfile::Custom {
+            let (min, max) = (self.min_refresh_rate, self.max_refresh_rate);
+            let range = monitor.min_refresh_rate..=monitor.max_refresh_rate;
+            if !range.contains(&min) || !range.contains(&max) || min >= max {
+                return Err(ArcSyncError::RefreshRateOutOfRange {
+                    min,
+                    max,
+                    monitor_min: monitor.min_refresh_rate,
+                    monitor_max: monitor.max_refresh_rate,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl From<ctl_intel_arc_sync_profile_params_t> for ArcSyncProfileParams {
+    fn from(params: ctl_intel_arc_sync_profile_params_t) -> Self {
+        Self {
+            profile: params.IntelArcSyncProfile.into(),
+            min_refresh_rate: params.MinRefreshRateInHz,
+            max_refresh_rate: params.MaxRefreshRateInHz,
+            max_frame_time_increase: params.MaxFrameTimeIncreaseInUs,
+            max_frame_time_decrease: params.MaxFrameTimeDecreaseInUs,
+        }
+    }
+}
+
+impl From<ArcSyncProfileParams> for ctl_intel_arc_sync_profile_params_t {
+    fn from(params: ArcSyncProfileParams) -> Self {
+        Self {
+            Size: std::mem::size_of::<ctl_intel_arc_sync_profile_params_t>() as u32,
+            Version: 0,
+            IntelArcSyncProfile: params.profile.into(),
+            MaxRefreshRateInHz: params.max_refresh_rate,
+            MinRefreshRateInHz: params.min_refresh_rate,
+            MaxFrameTimeIncreaseInUs: params.max_frame_time_increase,
+            MaxFrameTimeDecreaseInUs: params.max_frame_time_decrease,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ArcSyncError {
+    /// The monitor does not support Intel Arc Sync.
+    Unsupported,
+    /// [`ArcSyncProfile::Invalid`] or a profile not known to these bindings was given.
+    InvalidProfile(ArcSyncProfile),
+    /// The refresh rate range of a custom profile is empty or exceeds the one of the monitor.
+    RefreshRateOutOfRange {
+        min: f32,
+        max: f32,
+        monitor_min: f32,
+        monitor_max: f32,
+    },
+    Igcl(Error),
+}
+
+impl fmt::Display for ArcSyncError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unsupported => f.write_str("Intel Arc Sync is not supported by the monitor"),
+            Self::InvalidProfile(profile) => {
+                write!(f, "Intel Arc Sync profile {profile:?} cannot be set")
+            }
+            Self::RefreshRateOutOfRange {
+                min,
+                max,
+                monitor_min,
+                monitor_max,
+            } => write!(
+                f,
+                "refresh rate range {min}..={max} Hz is not within the monitor range of {monitor_min}..={monitor_max} Hz"
+            ),
+            Self::Igcl(error) => fmt::Display::fmt(error, f),
+        }
+    }
+}
+
+impl std::error::Error for ArcSyncError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Igcl(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<Error> for ArcSyncError {
+    fn from(error: Error) -> Self {
+        Self::Igcl(error)
+    }
+}
+
+impl DisplayOutput {
+    #[doc(alias = "ctlGetIntelArcSyncInfoForMonitor")]
+    pub fn arc_sync_monitor_info(&self) -> Result<ArcSyncMonitorInfo> {
+        let mut params = ctl_intel_arc_sync_monitor_params_t {
+            Size: std::mem::size_of::<ctl_intel_arc_sync_monitor_params_t>() as u32,
+            Version: 0,
+            ..Default::default()
+        };
+
+        Error::from_result(unsafe {
+            self.control_lib
+                .ctlGetIntelArcSyncInfoForMonitor(self.display_output_handle, &mut params)
+        })?;
+
+        Ok(params.into())
+    }
+
+    #[doc(alias = "ctlGetIntelArcSyncProfile")]
+    pub fn arc_sync_profile(&self) -> Result<ArcSyncProfileParams> {
+        let mut params = ctl_intel_arc_sync_profile_params_t {
+            Size: std::mem::size_of::<ctl_intel_arc_sync_profile_params_t>() as u32,
+            Version: 0,
+            ..Default::default()
+        };
+
+        Error::from_result(unsafe {
+            self.control_lib
+                .ctlGetIntelArcSyncProfile(self.display_output_handle, &mut params)
+        })?;
+
+        Ok(params.into())
+    }
+
+    /// Apply an Intel Arc Sync profile, after validating it with
+    /// [`ArcSyncProfileParams::validate()`] against [`Self::arc_sync_monitor_info()`]. The
+    /// constraints of profiles other than [`ArcSyncProfile::Custom`] are ignored.
+    #[doc(alias = "ctlSetIntelArcSyncProfile")]
+    pub fn set_arc_sync_profile(&self, params: ArcSyncProfileParams) -> Result<(), ArcSyncError> {
+        params.validate(&self.arc_sync_monitor_info()?)?;
+
+        let params = if params.profile == ArcSyncProfile::Custom {
+            params
+        } else {
+            ArcSyncProfileParams::new(params.profile)
+        };
+
+        let mut args = params.into();
+        Error::from_result(unsafe {
+            self.control_lib
+                .ctlSetIntelArcSyncProfile(self.display_output_handle, &mut args)
+        })?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MONITOR: ArcSyncMonitorInfo = ArcSyncMonitorInfo {
+        supported: true,
+        min_refresh_rate: 48.0,
+        max_refresh_rate: 144.0,
+        max_frame_time_increase: 0,
+        max_frame_time_decrease: 0,
+    };
+
+    fn custom(min: f32, max: f32) -> ArcSyncProfileParams {
+        ArcSyncProfileParams::custom(min, max, 0, 0)
+    }
+
+    #[test]
+    fn custom_range() {
+        assert!(custom(48.0, 144.0).validate(&MONITOR).is_ok());
+        assert!(custom(60.0, 120.0).validate(&MONITOR).is_ok());
+
+        for (min, max) in [
+            (40.0, 144.0),
+            (48.0, 165.0),
+            (120.0, 60.0),
+            (60.0, 60.0),
+            (f32::NAN, 144.0),
+        ] {
+            assert!(
+                matches!(
+                    custom(min, max).validate(&MONITOR),
+                    Err(ArcSyncError::RefreshRateOutOfRange {
+                        monitor_min: 48.0,
+                        monitor_max: 144.0,
+                        ..
+                    })
+                ),
+                "{min}..={max}"
+            );
+        }
+    }
+
+    #[test]
+    fn predefined_profiles_ignore_range() {
+        let params = ArcSyncProfileParams {
+            min_refresh_rate: 1000.0,
+            ..ArcSyncProfileParams::new(ArcSyncProfile::Excellent)
+        };
+        assert!(params.validate(&MONITOR).is_ok());
+    }
+
+    #[test]
+    fn invalid_profile() {
+        for profile in [ArcSyncProfile::Invalid, ArcSyncProfile::Unknown(42)] {
+            assert!(matches!(
+                ArcSyncProfileParams::new(profile).validate(&MONITOR),
+                Err(ArcSyncError::InvalidProfile(p)) if p == profile
+            ));
+        }
+    }
+
+    #[test]
+    fn unsupported_monitor() {
+        let monitor = ArcSyncMonitorInfo {
+            supported: false,
+            ..MONITOR
+        };
+        assert!(matches!(
+            ArcSyncProfileParams::new(ArcSyncProfile::Recommended).validate(&monitor),
+            Err(ArcSyncError::Unsupported)
+        ));
+    }
+
+    #[test]
+    fn params_round_trip() {
+        let params = custom(60.0, 120.0);
+        let raw: ctl_intel_arc_sync_profile_params_t = params.into();
+        assert_eq!(ArcSyncProfileParams::from(raw), params);
+        assert_eq!(
+            ArcSyncProfile::from(ctl_intel_arc_sync_profile_t::from(ArcSyncProfile::Unknown(
+                42
+            ))),
+            ArcSyncProfile::Unknown(42)
+        );
+    }
+}
@@ -0,0 +1,205 @@
+use std::{fmt, thread, time::Duration};
+
+use crate::{
+    display::DisplayOutput,
+    error::{Error, Result},
+    ffi::{ctl_get_brightness_t, ctl_set_brightness_t},
+};
+
+/// Full brightness, in milli-percent.
+pub const MAX_BRIGHTNESS: u32 = 100_000;
+
+/// Longest transition time the driver accepts for a single brightness change.
+pub const MAX_SMOOTH_TRANSITION: Duration = Duration::from_millis(1000);
+
+/// Brightness levels in milli-percent, i.e. `0..=100_000`.
+#[doc(alias = "ctl_get_brightness_t")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Brightness {
+    /// The level the display is transitioning to, equal to [`Self::current`] outside of a
+    /// transition.
+    pub target: u32,
+    pub current: u32,
+}
+
+#[derive(Debug)]
+pub enum BrightnessError {
+    /// The target brightness exceeds [`MAX_BRIGHTNESS`].
+    TargetOutOfRange(u32),
+    /// The transition time exceeds [`MAX_SMOOTH_TRANSITION`].
+    TransitionTooLong(Duration),
+    Igcl(Error),
+}
+
+impl fmt::Display for BrightnessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TargetOutOfRange(target) => write!(
+                f,
+                "target brightness {target} exceeds the maximum of {MAX_BRIGHTNESS}"
+            ),
+            Self::TransitionTooLong(transition) => write!(
+                f,
+                "transition time {transition:?} exceeds the maximum of {MAX_SMOOTH_TRANSITION:?}"
+            ),
+            Self::Igcl(error) => fmt::Display::fmt(error, f),
+        }
+    }
+}
+
+impl std::error::Error for BrightnessError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Igcl(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<Error> for BrightnessError {
+    fn from(error: Error) -> Self {
+        Self::Igcl(error)
+    }
+}
+
+/// Levels and transition times of the steps fading from `start` to `target` over
+/// `duration`, each no longer than [`MAX_SMOOTH_TRANSITION`].
+fn fade_steps(start: u32, target: u32, duration: Duration) -> Vec<(u32, Duration)> {
+    let steps = duration
+        .as_millis()
+        .div_ceil(MAX_SMOOTH_TRANSITION.as_millis())
+        .max(1) as u32;
+    let step_duration = duration / steps;
+    let start = i64::from(start);
+    let delta = i64::from(target) - start;
+
+    (1..=steps)
+        .map(|step| {
+            let level = start + delta * i64::from(step) / i64::from(steps);
+            (level as u32, step_duration)
+        })
+        .collect()
+}
+
+impl DisplayOutput {
+    #[doc(alias = "ctlGetBrightnessSetting")]
+    pub fn brightness(&self) -> Result<Brightness> {
+        let mut args = ctl_get_brightness_t {
+            Size: std::mem::size_of::<ctl_get_brightness_t>() as u32,
+            Version: 0,
+            ..Default::default()
+        };
+
+        Error::from_result(unsafe {
+            self.control_lib
+                .ctlGetBrightnessSetting(self.display_output_handle, &mut args)
+        })?;
+
+        Ok(Brightness {
+            target: args.TargetBrightness,
+            current: args.CurrentBrightness,
+        })
+    }
+
+    /// Start a transition to `target` milli-percent, taking `transition` to complete. A zero
+    /// transition time changes the brightness immediately.
+    #[doc(alias = "ctlSetBrightnessSetting")]
+    pub fn set_brightness(&self, target: u32, transition: Duration) -> Result<(), BrightnessError> {
+        if target > MAX_BRIGHTNESS {
+            return Err(BrightnessError::TargetOutOfRange(target));
+        }
+        if transition > MAX_SMOOTH_TRANSITION {
+            return Err(BrightnessError::TransitionTooLong(transition));
+        }
+
+        let mut args = ctl_set_brightness_t {
+            Size: std::mem::size_of::<ctl_set_brightness_t>() as u32,
+            Version: 0,
+            TargetBrightness: target,
+            SmoothTransitionTimeInMs: transition.as_millis() as u32,
+            ..Default::default()
+        };
+
+        Error::from_result(unsafe {
+            self.control_lib
+                .ctlSetBrightnessSetting(self.display_output_handle, &mut args)
+        })?;
+
+        Ok(())
+    }
+
+    /// Fade to `target` milli-percent over `duration`, which may exceed
+    /// [`MAX_SMOOTH_TRANSITION`]. Longer fades are split into evenly sized steps, and this
+    /// blocks until the last step has completed. Returns the brightness read back afterwards.
+    pub fn fade_to(&self, target: u32, duration: Duration) -> Result<Brightness, BrightnessError> {
+        if target > MAX_BRIGHTNESS {
+            return Err(BrightnessError::TargetOutOfRange(target));
+        }
+
+        let start = self.brightness()?.current;
+        for (level, step_duration) in fade_steps(start, target, duration) {
+            self.set_brightness(level, step_duration)?;
+            thread::sleep(step_duration);
+        }
+
+        Ok(self.brightness()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fade_steps_zero_duration() {
+        assert_eq!(
+            fade_steps(20_000, 80_000, Duration::ZERO),
+            [(80_000, Duration::ZERO)]
+        );
+    }
+
+    #[test]
+    fn fade_steps_single() {
+        assert_eq!(
+            fade_steps(20_000, 80_000, MAX_SMOOTH_TRANSITION),
+            [(80_000, MAX_SMOOTH_TRANSITION)]
+        );
+    }
+
+    #[test]
+    fn fade_steps_uneven_duration() {
+        assert_eq!(
+            fade_steps(0, 30_000, Duration::from_millis(2500)),
+            [
+                (10_000, Duration::from_nanos(833_333_333)),
+                (20_000, Duration::from_nanos(833_333_333)),
+                (30_000, Duration::from_nanos(833_333_333)),
+            ]
+        );
+
+        let steps = fade_steps(0, MAX_BRIGHTNESS, Duration::from_millis(1001));
+        assert_eq!(steps.len(), 2);
+        assert!(steps.iter().all(|&(_, d)| d <= MAX_SMOOTH_TRANSITION));
+        assert_eq!(steps.last().unwrap().0, MAX_BRIGHTNESS);
+    }
+
+    #[test]
+    fn fade_steps_down() {
+        assert_eq!(
+            fade_steps(100_000, 10_000, Duration::from_secs(3)),
+            [
+                (70_000, Duration::from_secs(1)),
+                (40_000, Duration::from_secs(1)),
+                (10_000, Duration::from_secs(1)),
+            ]
+        );
+
+        // Levels round towards the start and never overshoot the target
+        let steps = fade_steps(100, 0, Duration::from_secs(3));
+        assert_eq!(
+            steps.iter().map(|&(level, _)| level).collect::<Vec<_>>(),
+            [67, 34, 0]
+        );
+    }
+}
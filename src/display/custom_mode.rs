@@ -0,0 +1,166 @@
+use std::fmt;
+
+use crate::{
+    display::DisplayOutput,
+    error::{Error, Result},
+    ffi::{
+        ctl_custom_mode_operation_types_t, ctl_custom_src_mode_t, ctl_get_set_custom_mode_args_t,
+        ctl_result_t,
+    },
+};
+
+/// Custom source mode, i.e. a resolution that is exposed to the OS on top of the modes
+/// reported by the display.
+#[doc(alias = "ctl_custom_src_mode_t")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CustomSourceMode {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl From<ctl_custom_src_mode_t> for CustomSourceMode {
+    fn from(mode: ctl_custom_src_mode_t) -> Self {
+        Self {
+            width: mode.SourceX,
+            height: mode.SourceY,
+        }
+    }
+}
+
+impl From<CustomSourceMode> for ctl_custom_src_mode_t {
+    fn from(mode: CustomSourceMode) -> Self {
+        Self {
+            SourceX: mode.width,
+            SourceY: mode.height,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum CustomModeError {
+    /// A standard custom mode with the same resolution already exists.
+    #[doc(alias = "CTL_RESULT_ERROR_CUSTOM_MODE_STANDARD_CUSTOM_MODE_EXISTS")]
+    StandardModeExists,
+    /// A regular (non-custom) mode with the same resolution already exists.
+    #[doc(alias = "CTL_RESULT_ERROR_CUSTOM_MODE_NON_CUSTOM_MATCHING_MODE_EXISTS")]
+    NonCustomMatchingModeExists,
+    /// The driver has no room left to store another custom mode.
+    #[doc(alias = "CTL_RESULT_ERROR_CUSTOM_MODE_INSUFFICIENT_MEMORY")]
+    InsufficientMemory,
+    Igcl(Error),
+}
+
+impl fmt::Display for CustomModeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::StandardModeExists => fmt::Display::fmt(
+                &Error(ctl_result_t::CTL_RESULT_ERROR_CUSTOM_MODE_STANDARD_CUSTOM_MODE_EXISTS),
+                f,
+            ),
+            Self::NonCustomMatchingModeExists => fmt::Display::fmt(
+                &Error(ctl_result_t::CTL_RESULT_ERROR_CUSTOM_MODE_NON_CUSTOM_MATCHING_MODE_EXISTS),
+                f,
+            ),
+            Self::InsufficientMemory => fmt::Display::fmt(
+                &Error(ctl_result_t::CTL_RESULT_ERROR_CUSTOM_MODE_INSUFFICIENT_MEMORY),
+                f,
+            ),
+            Self::Igcl(error) => fmt::Display::fmt(error, f),
+        }
+    }
+}
+
+impl std::error::Error for CustomModeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Igcl(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<Error> for CustomModeError {
+    fn from(error: Error) -> Self {
+        match error.0 {
+            ctl_result_t::CTL_RESULT_ERROR_CUSTOM_MODE_STANDARD_CUSTOM_MODE_EXISTS => {
+                Self::StandardModeExists
+            }
+            ctl_result_t::CTL_RESULT_ERROR_CUSTOM_MODE_NON_CUSTOM_MATCHING_MODE_EXISTS => {
+                Self::NonCustomMatchingModeExists
+            }
+            ctl_result_t::CTL_RESULT_ERROR_CUSTOM_MODE_INSUFFICIENT_MEMORY => {
+                Self::InsufficientMemory
+            }
+            _ => Self::Igcl(error),
+        }
+    }
+}
+
+impl DisplayOutput {
+    fn get_set_custom_mode(
+        &self,
+        op_type: ctl_custom_mode_operation_types_t,
+        modes: &mut [ctl_custom_src_mode_t],
+    ) -> Result<u32> {
+        let mut args = ctl_get_set_custom_mode_args_t {
+            Size: std::mem::size_of::<ctl_get_set_custom_mode_args_t>() as u32,
+            Version: 0,
+            CustomModeOpType: op_type,
+            NumOfModes: modes.len() as u32,
+            pCustomSrcModeList: if modes.is_empty() {
+                std::ptr::null_mut()
+            } else {
+                modes.as_mut_ptr()
+            },
+        };
+
+        Error::from_result(unsafe {
+            self.control_lib
+                .ctlGetSetCustomMode(self.display_output_handle, &mut args)
+        })?;
+
+        Ok(args.NumOfModes)
+    }
+
+    /// List the custom source modes previously added to this output.
+    #[doc(alias = "ctlGetSetCustomMode")]
+    #[doc(alias = "CTL_CUSTOM_MODE_OPERATION_TYPES_GET_CUSTOM_SOURCE_MODES")]
+    pub fn custom_modes(&self) -> Result<Vec<CustomSourceMode>> {
+        let op_type =
+            ctl_custom_mode_operation_types_t::CTL_CUSTOM_MODE_OPERATION_TYPES_GET_CUSTOM_SOURCE_MODES;
+
+        let num_modes = self.get_set_custom_mode(op_type, &mut [])?;
+
+        let mut modes = vec![ctl_custom_src_mode_t::default(); num_modes as usize];
+        let num_modes = self.get_set_custom_mode(op_type, &mut modes)?;
+        modes.truncate(num_modes as usize);
+
+        Ok(modes.into_iter().map(CustomSourceMode::from).collect())
+    }
+
+    /// Add a single custom source mode to this output.
+    #[doc(alias = "CTL_CUSTOM_MODE_OPERATION_TYPES_ADD_CUSTOM_SOURCE_MODE")]
+    pub fn add_custom_mode(&self, mode: CustomSourceMode) -> Result<(), CustomModeError> {
+        self.get_set_custom_mode(
+            ctl_custom_mode_operation_types_t::CTL_CUSTOM_MODE_OPERATION_TYPES_ADD_CUSTOM_SOURCE_MODE,
+            &mut [mode.into()],
+        )?;
+        Ok(())
+    }
+
+    /// Remove one or more previously added custom source modes from this output.
+    #[doc(alias = "CTL_CUSTOM_MODE_OPERATION_TYPES_REMOVE_CUSTOM_SOURCE_MODES")]
+    pub fn remove_custom_modes(&self, modes: &[CustomSourceMode]) -> Result<(), CustomModeError> {
+        if modes.is_empty() {
+            return Ok(());
+        }
+
+        let mut modes: Vec<ctl_custom_src_mode_t> = modes.iter().map(|&m| m.into()).collect();
+        self.get_set_custom_mode(
+            ctl_custom_mode_operation_types_t::CTL_CUSTOM_MODE_OPERATION_TYPES_REMOVE_CUSTOM_SOURCE_MODES,
+            &mut modes,
+        )?;
+        Ok(())
+    }
+}
@@ -0,0 +1,181 @@
+use std::fmt;
+
+use crate::{
+    display::DisplayOutput,
+    error::{Error, Result},
+    ffi::ctl_dce_args_t,
+};
+
+/// Dynamic contrast enhancement state, with the histogram of the current frame.
+#[doc(alias = "ctl_dce_args_t")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DynamicContrastEnhancement {
+    pub supported: bool,
+    pub enabled: bool,
+    /// Pixel count per bin, empty while DCE is disabled.
+    pub histogram: Vec<u32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DceSettings {
+    pub enable: bool,
+    pub target_brightness_percent: u32,
+    /// Multiplier for the speed at which the target brightness is phased in.
+    pub phase_in_speed_multiplier: f64,
+}
+
+impl DceSettings {
+    /// Check that the target brightness is at most 100% and the phase-in speed multiplier is
+    /// a positive number.
+    pub fn validate(&self) -> Result<(), DceError> {
+        if self.target_brightness_percent > 100 {
+            return Err(DceError::TargetBrightnessOutOfRange(
+                self.target_brightness_percent,
+            ));
+        }
+        if !self.phase_in_speed_multiplier.is_finite() || self.phase_in_speed_multiplier <= 0.0 {
+            return Err(DceError::InvalidPhaseInSpeed(
+                self.phase_in_speed_multiplier,
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub enum DceError {
+    /// The target brightness exceeds 100%.
+    TargetBrightnessOutOfRange(u32),
+    /// The phase-in speed multiplier is not a positive number.
+    InvalidPhaseInSpeed(f64),
+    Igcl(Error),
+}
+
+impl fmt::Display for DceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TargetBrightnessOutOfRange(percent) => {
+                write!(f, "target brightness {percent}% exceeds 100%")
+            }
+            Self::InvalidPhaseInSpeed(multiplier) => write!(
+                f,
+                "phase-in speed multiplier {multiplier} is not a positive number"
+            ),
+            Self::Igcl(error) => fmt::Display::fmt(error, f),
+        }
+    }
+}
+
+impl std::error::Error for DceError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Igcl(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<Error> for DceError {
+    fn from(error: Error) -> Self {
+        Self::Igcl(error)
+    }
+}
+
+impl DisplayOutput {
+    /// Query the DCE state and, while enabled, the histogram of the current frame.
+    #[doc(alias = "ctlGetSetDynamicContrastEnhancement")]
+    pub fn dynamic_contrast_enhancement(&self) -> Result<DynamicContrastEnhancement> {
+        let mut args = ctl_dce_args_t {
+            Size: std::mem::size_of::<ctl_dce_args_t>() as u32,
+            Version: 0,
+            Set: false,
+            ..Default::default()
+        };
+
+        Error::from_result(unsafe {
+            self.control_lib
+                .ctlGetSetDynamicContrastEnhancement(self.display_output_handle, &mut args)
+        })?;
+
+        let mut histogram = vec![];
+        if args.Enable && args.NumBins > 0 {
+            histogram.resize(args.NumBins as usize, 0);
+            args.pHistogram = histogram.as_mut_ptr();
+
+            Error::from_result(unsafe {
+                self.control_lib
+                    .ctlGetSetDynamicContrastEnhancement(self.display_output_handle, &mut args)
+            })?;
+
+            histogram.truncate(args.NumBins as usize);
+        }
+
+        Ok(DynamicContrastEnhancement {
+            supported: args.IsSupported,
+            enabled: args.Enable,
+            histogram,
+        })
+    }
+
+    /// Enable or disable DCE and phase in the target brightness, after validating `settings`
+    /// with [`DceSettings::validate()`].
+    pub fn set_dynamic_contrast_enhancement(&self, settings: DceSettings) -> Result<(), DceError> {
+        settings.validate()?;
+
+        let mut args = ctl_dce_args_t {
+            Size: std::mem::size_of::<ctl_dce_args_t>() as u32,
+            Version: 0,
+            Set: true,
+            TargetBrightnessPercent: settings.target_brightness_percent,
+            PhaseinSpeedMultiplier: settings.phase_in_speed_multiplier,
+            Enable: settings.enable,
+            ..Default::default()
+        };
+
+        Error::from_result(unsafe {
+            self.control_lib
+                .ctlGetSetDynamicContrastEnhancement(self.display_output_handle, &mut args)
+        })?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(target_brightness_percent: u32, phase_in_speed_multiplier: f64) -> DceSettings {
+        DceSettings {
+            enable: true,
+            target_brightness_percent,
+            phase_in_speed_multiplier,
+        }
+    }
+
+    #[test]
+    fn valid() {
+        assert!(settings(0, 1.0).validate().is_ok());
+        assert!(settings(100, 0.25).validate().is_ok());
+    }
+
+    #[test]
+    fn target_brightness() {
+        assert!(matches!(
+            settings(101, 1.0).validate(),
+            Err(DceError::TargetBrightnessOutOfRange(101))
+        ));
+    }
+
+    #[test]
+    fn phase_in_speed() {
+        for multiplier in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert!(matches!(
+                settings(50, multiplier).validate(),
+                Err(DceError::InvalidPhaseInSpeed(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,554 @@
+use std::fmt;
+
+use crate::{
+    display::DisplayOutput,
+    error::{Error, Result},
+    ffi::{
+        ctl_edid_management_args_t, ctl_edid_management_optype_t, ctl_edid_management_out_flag_t,
+        ctl_edid_type_t,
+    },
+};
+
+/// Size of a single EDID block, the base block as well as every extension block.
+pub const EDID_BLOCK_SIZE: usize = 128;
+
+const EDID_HEADER: [u8; 8] = [0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00];
+
+#[doc(alias = "ctl_edid_type_t")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EdidType {
+    /// The currently active EDID, which is either the monitor's or a supplied one.
+    Current,
+    /// The EDID previously supplied through an override or lock.
+    Override,
+    /// The EDID reported by the monitor itself.
+    Monitor,
+}
+
+impl From<EdidType> for ctl_edid_type_t {
+    fn from(edid_type: EdidType) -> Self {
+        match edid_type {
+            EdidType::Current => Self::CTL_EDID_TYPE_CURRENT,
+            EdidType::Override => Self::CTL_EDID_TYPE_OVERRIDE,
+            EdidType::Monitor => Self::CTL_EDID_TYPE_MONITOR,
+        }
+    }
+}
+
+bitflags::bitflags! {
+    #[doc(alias = "ctl_edid_management_out_flags_t")]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct EdidManagementOutFlags: u32 {
+        /// The OS was notified about a connection change, and the application needs to
+        /// wait for the OS to act on it.
+        const OS_CONN_NOTIFICATION = ctl_edid_management_out_flag_t::CTL_EDID_MANAGEMENT_OUT_FLAG_OS_CONN_NOTIFICATION.0 as u32;
+        /// The current EDID was previously supplied.
+        const SUPPLIED_EDID = ctl_edid_management_out_flag_t::CTL_EDID_MANAGEMENT_OUT_FLAG_SUPPLIED_EDID.0 as u32;
+        /// The current EDID is the monitor's.
+        const MONITOR_EDID = ctl_edid_management_out_flag_t::CTL_EDID_MANAGEMENT_OUT_FLAG_MONITOR_EDID.0 as u32;
+        /// The monitor is physically connected.
+        const DISPLAY_CONNECTED = ctl_edid_management_out_flag_t::CTL_EDID_MANAGEMENT_OUT_FLAG_DISPLAY_CONNECTED.0 as u32;
+    }
+}
+
+/// Reasons for which an EDID blob is rejected by [`Edid::validate()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EdidValidationError {
+    /// The blob is empty or not a multiple of [`EDID_BLOCK_SIZE`].
+    InvalidLength(usize),
+    /// The base block does not start with the fixed EDID header pattern.
+    InvalidHeader,
+    /// The bytes of a block do not sum to zero.
+    InvalidChecksum { block: usize },
+    /// The base block announces a different number of extension blocks than present.
+    ExtensionCountMismatch { announced: usize, present: usize },
+}
+
+impl fmt::Display for EdidValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLength(len) => write!(
+                f,
+                "EDID length {len} is not a non-zero multiple of {EDID_BLOCK_SIZE}"
+            ),
+            Self::InvalidHeader => f.write_str("EDID header pattern is missing"),
+            Self::InvalidChecksum { block } => write!(f, "EDID block {block} has a bad checksum"),
+            Self::ExtensionCountMismatch { announced, present } => write!(
+                f,
+                "EDID announces {announced} extension blocks but contains {present}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for EdidValidationError {}
+
+/// Error returned by EDID operations that validate their input before calling into IGCL.
+#[derive(Debug)]
+pub enum EdidError {
+    Invalid(EdidValidationError),
+    Igcl(Error),
+}
+
+impl fmt::Display for EdidError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Invalid(error) => write!(f, "invalid EDID: {error}"),
+            Self::Igcl(error) => fmt::Display::fmt(error, f),
+        }
+    }
+}
+
+impl std::error::Error for EdidError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Invalid(error) => Some(error),
+            Self::Igcl(error) => Some(error),
+        }
+    }
+}
+
+impl From<EdidValidationError> for EdidError {
+    fn from(error: EdidValidationError) -> Self {
+        Self::Invalid(error)
+    }
+}
+
+impl From<Error> for EdidError {
+    fn from(error: Error) -> Self {
+        Self::Igcl(error)
+    }
+}
+
+/// Owned EDID blob, consisting of a base block and any extension blocks.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Edid(pub Vec<u8>);
+
+impl Edid {
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Validate the length, header, extension count and checksum of every block.
+    pub fn validate(&self) -> Result<(), EdidValidationError> {
+        let bytes = &self.0;
+
+        if bytes.is_empty() || bytes.len() % EDID_BLOCK_SIZE != 0 {
+            return Err(EdidValidationError::InvalidLength(bytes.len()));
+        }
+
+        if bytes[..EDID_HEADER.len()] != EDID_HEADER {
+            return Err(EdidValidationError::InvalidHeader);
+        }
+
+        for (block, data) in bytes.chunks_exact(EDID_BLOCK_SIZE).enumerate() {
+            if data.iter().fold(0u8, |sum, &b| sum.wrapping_add(b)) != 0 {
+                return Err(EdidValidationError::InvalidChecksum { block });
+            }
+        }
+
+        let announced = bytes[126] as usize;
+        let present = bytes.len() / EDID_BLOCK_SIZE - 1;
+        if announced != present {
+            return Err(EdidValidationError::ExtensionCountMismatch { announced, present });
+        }
+
+        Ok(())
+    }
+
+    /// Validate and decode the fields of the base block.
+    pub fn parse(&self) -> Result<EdidInfo, EdidValidationError> {
+        self.validate()?;
+
+        let base = &self.0[..EDID_BLOCK_SIZE];
+
+        let manufacturer = u16::from_be_bytes([base[8], base[9]]);
+        let manufacturer_id = [10, 5, 0]
+            .into_iter()
+            .map(|shift| char::from(b'A' - 1 + ((manufacturer >> shift) & 0x1f) as u8))
+            .collect();
+
+        let mut info = EdidInfo {
+            manufacturer_id,
+            product_code: u16::from_le_bytes([base[10], base[11]]),
+            serial_number: u32::from_le_bytes([base[12], base[13], base[14], base[15]]),
+            manufacture_week: base[16],
+            manufacture_year: 1990 + u16::from(base[17]),
+            version: (base[18], base[19]),
+            display_name: None,
+            serial_string: None,
+            preferred_timing: None,
+        };
+
+        for descriptor in base[54..126].chunks_exact(18) {
+            if descriptor[0] != 0 || descriptor[1] != 0 {
+                // The first detailed timing descriptor is the preferred timing
+                if info.preferred_timing.is_none() {
+                    info.preferred_timing = Some(DetailedTiming::parse(descriptor));
+                }
+                continue;
+            }
+
+            match descriptor[3] {
+                0xfc => info.display_name = Some(descriptor_text(descriptor)),
+                0xff => info.serial_string = Some(descriptor_text(descriptor)),
+                _ => {}
+            }
+        }
+
+        Ok(info)
+    }
+}
+
+impl From<Vec<u8>> for Edid {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+}
+
+/// Decode the text of a display descriptor, terminated by a line feed and padded with spaces.
+fn descriptor_text(descriptor: &[u8]) -> String {
+    let text = &descriptor[5..18];
+    let end = text.iter().position(|&b| b == b'\n').unwrap_or(text.len());
+    String::from_utf8_lossy(&text[..end]).trim_end().to_owned()
+}
+
+/// Fields decoded from the base block of an [`Edid`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EdidInfo {
+    /// Three-letter PNP manufacturer ID, e.g. `DEL`.
+    pub manufacturer_id: String,
+    pub product_code: u16,
+    pub serial_number: u32,
+    /// Week of manufacture, or `0`/`0xff` if not specified.
+    pub manufacture_week: u8,
+    pub manufacture_year: u16,
+    /// EDID version and revision, e.g. `(1, 4)`.
+    pub version: (u8, u8),
+    pub display_name: Option<String>,
+    pub serial_string: Option<String>,
+    pub preferred_timing: Option<DetailedTiming>,
+}
+
+/// Detailed timing descriptor from an [`Edid`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DetailedTiming {
+    /// Pixel clock in Hz.
+    pub pixel_clock: u64,
+    pub h_active: u32,
+    pub h_blank: u32,
+    pub v_active: u32,
+    pub v_blank: u32,
+    pub h_sync_offset: u32,
+    pub h_sync_width: u32,
+    pub v_sync_offset: u32,
+    pub v_sync_width: u32,
+    /// Horizontal image size in millimeters.
+    pub h_image_size: u32,
+    /// Vertical image size in millimeters.
+    pub v_image_size: u32,
+    pub interlaced: bool,
+}
+
+impl DetailedTiming {
+    fn parse(d: &[u8]) -> Self {
+        let b = |i: usize| u32::from(d[i]);
+
+        Self {
+            pixel_clock: u64::from(b(0) | b(1) << 8) * 10_000,
+            h_active: b(2) | (b(4) & 0xf0) << 4,
+            h_blank: b(3) | (b(4) & 0x0f) << 8,
+            v_active: b(5) | (b(7) & 0xf0) << 4,
+            v_blank: b(6) | (b(7) & 0x0f) << 8,
+            h_sync_offset: b(8) | (b(11) >> 6 & 0x3) << 8,
+            h_sync_width: b(9) | (b(11) >> 4 & 0x3) << 8,
+            v_sync_offset: b(10) >> 4 | (b(11) >> 2 & 0x3) << 4,
+            v_sync_width: b(10) & 0x0f | (b(11) & 0x3) << 4,
+            h_image_size: b(12) | (b(14) & 0xf0) << 4,
+            v_image_size: b(13) | (b(14) & 0x0f) << 8,
+            interlaced: d[17] & 0x80 != 0,
+        }
+    }
+
+    pub fn h_total(&self) -> u32 {
+        self.h_active + self.h_blank
+    }
+
+    pub fn v_total(&self) -> u32 {
+        self.v_active + self.v_blank
+    }
+
+    /// Refresh rate in Hz, derived from the pixel clock and total timings.
+    pub fn refresh_rate(&self) -> f64 {
+        let total = u64::from(self.h_total()) * u64::from(self.v_total());
+        if total == 0 {
+            return 0.0;
+        }
+        self.pixel_clock as f64 / total as f64
+    }
+}
+
+impl DisplayOutput {
+    fn edid_management(
+        &self,
+        op_type: ctl_edid_management_optype_t,
+        edid_type: ctl_edid_type_t,
+        edid: Option<&Edid>,
+    ) -> Result<EdidManagementOutFlags> {
+        // IGCL takes a mutable pointer even for operations that only read the buffer
+        let mut buffer = edid.map(|edid| edid.0.clone()).unwrap_or_default();
+
+        let mut args = ctl_edid_management_args_t {
+            Size: std::mem::size_of::<ctl_edid_management_args_t>() as u32,
+            Version: 0,
+            OpType: op_type,
+            EdidType: edid_type,
+            EdidSize: buffer.len() as u32,
+            pEdidBuf: if buffer.is_empty() {
+                std::ptr::null_mut()
+            } else {
+                buffer.as_mut_ptr()
+            },
+            OutFlags: 0,
+        };
+
+        Error::from_result(unsafe {
+            self.control_lib
+                .ctlEdidManagement(self.display_output_handle, &mut args)
+        })?;
+
+        Ok(EdidManagementOutFlags::from_bits_retain(args.OutFlags))
+    }
+
+    /// Read the EDID of the given type.
+    #[doc(alias = "ctlEdidManagement")]
+    #[doc(alias = "CTL_EDID_MANAGEMENT_OPTYPE_READ_EDID")]
+    pub fn read_edid(&self, edid_type: EdidType) -> Result<(Edid, EdidManagementOutFlags)> {
+        let mut args = ctl_edid_management_args_t {
+            Size: std::mem::size_of::<ctl_edid_management_args_t>() as u32,
+            Version: 0,
+            OpType: ctl_edid_management_optype_t::CTL_EDID_MANAGEMENT_OPTYPE_READ_EDID,
+            EdidType: edid_type.into(),
+            EdidSize: 0,
+            pEdidBuf: std::ptr::null_mut(),
+            OutFlags: 0,
+        };
+
+        Error::from_result(unsafe {
+            self.control_lib
+                .ctlEdidManagement(self.display_output_handle, &mut args)
+        })?;
+
+        let flags = EdidManagementOutFlags::from_bits_retain(args.OutFlags);
+        if args.EdidSize == 0 {
+            return Ok((Edid(vec![]), flags));
+        }
+
+        let mut buffer = vec![0u8; args.EdidSize as usize];
+        args.pEdidBuf = buffer.as_mut_ptr();
+
+        Error::from_result(unsafe {
+            self.control_lib
+                .ctlEdidManagement(self.display_output_handle, &mut args)
+        })?;
+
+        buffer.truncate(args.EdidSize as usize);
+
+        Ok((
+            Edid(buffer),
+            EdidManagementOutFlags::from_bits_retain(args.OutFlags),
+        ))
+    }
+
+    /// Keep the output connected with the monitor's EDID across unplugs and reboots.
+    #[doc(alias = "CTL_EDID_MANAGEMENT_OPTYPE_LOCK_EDID")]
+    pub fn lock_edid_to_monitor(&self) -> Result<EdidManagementOutFlags> {
+        self.edid_management(
+            ctl_edid_management_optype_t::CTL_EDID_MANAGEMENT_OPTYPE_LOCK_EDID,
+            ctl_edid_type_t::CTL_EDID_TYPE_MONITOR,
+            None,
+        )
+    }
+
+    /// Keep the output connected with the supplied `edid` across unplugs and reboots.
+    ///
+    /// The EDID is validated with [`Edid::validate()`] before it is passed to the driver.
+    #[doc(alias = "CTL_EDID_MANAGEMENT_OPTYPE_LOCK_EDID")]
+    pub fn lock_edid(&self, edid: &Edid) -> Result<EdidManagementOutFlags, EdidError> {
+        edid.validate()?;
+        Ok(self.edid_management(
+            ctl_edid_management_optype_t::CTL_EDID_MANAGEMENT_OPTYPE_LOCK_EDID,
+            ctl_edid_type_t::CTL_EDID_TYPE_OVERRIDE,
+            Some(edid),
+        )?)
+    }
+
+    /// Undo [`Self::lock_edid()`] or [`Self::lock_edid_to_monitor()`], after which the output
+    /// is reported as detached when unplugged again.
+    #[doc(alias = "CTL_EDID_MANAGEMENT_OPTYPE_UNLOCK_EDID")]
+    pub fn unlock_edid(&self) -> Result<EdidManagementOutFlags> {
+        self.edid_management(
+            ctl_edid_management_optype_t::CTL_EDID_MANAGEMENT_OPTYPE_UNLOCK_EDID,
+            ctl_edid_type_t::CTL_EDID_TYPE_OVERRIDE,
+            None,
+        )
+    }
+
+    /// Replace the EDID of the output with `edid`, without modifying the EDID stored in
+    /// the monitor. Cannot be applied to a locked output.
+    ///
+    /// The EDID is validated with [`Edid::validate()`] before it is passed to the driver.
+    #[doc(alias = "CTL_EDID_MANAGEMENT_OPTYPE_OVERRIDE_EDID")]
+    pub fn override_edid(&self, edid: &Edid) -> Result<EdidManagementOutFlags, EdidError> {
+        edid.validate()?;
+        Ok(self.edid_management(
+            ctl_edid_management_optype_t::CTL_EDID_MANAGEMENT_OPTYPE_OVERRIDE_EDID,
+            ctl_edid_type_t::CTL_EDID_TYPE_OVERRIDE,
+            Some(edid),
+        )?)
+    }
+
+    /// Remove a previous [`Self::override_edid()`], restoring the monitor's EDID.
+    #[doc(alias = "CTL_EDID_MANAGEMENT_OPTYPE_UNDO_OVERRIDE_EDID")]
+    pub fn undo_edid_override(&self) -> Result<EdidManagementOutFlags> {
+        self.edid_management(
+            ctl_edid_management_optype_t::CTL_EDID_MANAGEMENT_OPTYPE_UNDO_OVERRIDE_EDID,
+            ctl_edid_type_t::CTL_EDID_TYPE_OVERRIDE,
+            None,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Base block of a 1920x1080 monitor without extension blocks.
+    fn edid() -> Vec<u8> {
+        let mut bytes = vec![0u8; EDID_BLOCK_SIZE];
+        bytes[..8].copy_from_slice(&EDID_HEADER);
+        // "DEL", product 0xa0c4, serial 0x12345678, week 12 of 2020, EDID 1.4
+        bytes[8..20]
+            .copy_from_slice(&[0x10, 0xac, 0xc4, 0xa0, 0x78, 0x56, 0x34, 0x12, 12, 30, 1, 4]);
+        bytes[54..72].copy_from_slice(&[
+            0x02, 0x3a, 0x80, 0x18, 0x71, 0x38, 0x2d, 0x40, 0x58, 0x2c, 0x45, 0x00, 0x0f, 0x28,
+            0x21, 0x00, 0x00, 0x1e,
+        ]);
+        bytes[72..77].copy_from_slice(&[0, 0, 0, 0xfc, 0]);
+        bytes[77..90].copy_from_slice(b"DELL U2415\n  ");
+        bytes[90..95].copy_from_slice(&[0, 0, 0, 0xff, 0]);
+        bytes[95..108].copy_from_slice(b"ABC123\n      ");
+        fix_checksum(&mut bytes[..EDID_BLOCK_SIZE]);
+        bytes
+    }
+
+    fn fix_checksum(block: &mut [u8]) {
+        let sum = block[..EDID_BLOCK_SIZE - 1]
+            .iter()
+            .fold(0u8, |sum, &b| sum.wrapping_add(b));
+        block[EDID_BLOCK_SIZE - 1] = sum.wrapping_neg();
+    }
+
+    #[test]
+    fn parse() {
+        let info = Edid(edid()).parse().unwrap();
+
+        assert_eq!(info.manufacturer_id, "DEL");
+        assert_eq!(info.product_code, 0xa0c4);
+        assert_eq!(info.serial_number, 0x1234_5678);
+        assert_eq!(info.manufacture_week, 12);
+        assert_eq!(info.manufacture_year, 2020);
+        assert_eq!(info.version, (1, 4));
+        assert_eq!(info.display_name.as_deref(), Some("DELL U2415"));
+        assert_eq!(info.serial_string.as_deref(), Some("ABC123"));
+
+        let timing = info.preferred_timing.unwrap();
+        assert_eq!(
+            timing,
+            DetailedTiming {
+                pixel_clock: 148_500_000,
+                h_active: 1920,
+                h_blank: 280,
+                v_active: 1080,
+                v_blank: 45,
+                h_sync_offset: 88,
+                h_sync_width: 44,
+                v_sync_offset: 4,
+                v_sync_width: 5,
+                h_image_size: 527,
+                v_image_size: 296,
+                interlaced: false,
+            }
+        );
+        assert_eq!(timing.h_total(), 2200);
+        assert_eq!(timing.v_total(), 1125);
+        assert!((timing.refresh_rate() - 60.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn validate_extension_blocks() {
+        let mut bytes = edid();
+        bytes[126] = 1;
+        fix_checksum(&mut bytes);
+        bytes.extend_from_slice(&[0u8; EDID_BLOCK_SIZE]);
+        bytes[EDID_BLOCK_SIZE] = 0x02;
+        fix_checksum(&mut bytes[EDID_BLOCK_SIZE..]);
+        assert_eq!(Edid(bytes.clone()).validate(), Ok(()));
+
+        bytes.truncate(EDID_BLOCK_SIZE);
+        assert_eq!(
+            Edid(bytes).validate(),
+            Err(EdidValidationError::ExtensionCountMismatch {
+                announced: 1,
+                present: 0
+            })
+        );
+    }
+
+    #[test]
+    fn validate_corrupted() {
+        assert_eq!(
+            Edid(vec![]).validate(),
+            Err(EdidValidationError::InvalidLength(0))
+        );
+        assert_eq!(
+            Edid(edid()[..100].to_vec()).validate(),
+            Err(EdidValidationError::InvalidLength(100))
+        );
+
+        let mut bytes = edid();
+        bytes[0] = 0xff;
+        assert_eq!(
+            Edid(bytes).validate(),
+            Err(EdidValidationError::InvalidHeader)
+        );
+
+        let mut bytes = edid();
+        bytes[20] ^= 0x01;
+        assert_eq!(
+            Edid(bytes.clone()).validate(),
+            Err(EdidValidationError::InvalidChecksum { block: 0 })
+        );
+        assert_eq!(
+            Edid(bytes).parse(),
+            Err(EdidValidationError::InvalidChecksum { block: 0 })
+        );
+    }
+
+    #[test]
+    fn parse_without_descriptors() {
+        let mut bytes = edid();
+        bytes[54..126].fill(0);
+        fix_checksum(&mut bytes);
+
+        let info = Edid(bytes).parse().unwrap();
+        assert_eq!(info.display_name, None);
+        assert_eq!(info.serial_string, None);
+        assert_eq!(info.preferred_timing, None);
+    }
+}
@@ -0,0 +1,425 @@
+use std::fmt;
+
+use crate::{
+    display::DisplayOutput,
+    error::{Error, Result},
+    ffi::{
+        ctl_get_operation_flag_t, ctl_lace_aggr_config_t, ctl_lace_config_t,
+        ctl_lace_lux_aggr_map_entry_t, ctl_lace_lux_aggr_map_t, ctl_lace_trigger_flag_t,
+        ctl_result_t, ctl_set_operation_t,
+    },
+};
+
+bitflags::bitflags! {
+    #[doc(alias = "ctl_lace_trigger_flags_t")]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct LaceTriggerFlags: u32 {
+        /// Enhancement depends on the ambient light.
+        const AMBIENT_LIGHT = ctl_lace_trigger_flag_t::CTL_LACE_TRIGGER_FLAG_AMBIENT_LIGHT.0 as u32;
+        /// Enhancement uses a fixed aggressiveness level.
+        const FIXED_AGGRESSIVENESS = ctl_lace_trigger_flag_t::CTL_LACE_TRIGGER_FLAG_FIXED_AGGRESSIVENESS.0 as u32;
+    }
+}
+
+/// Highest aggressiveness level, in percent.
+pub const MAX_LACE_AGGRESSIVENESS: u8 = 100;
+
+/// Entry of the table mapping ambient light to enhancement aggressiveness.
+#[doc(alias = "ctl_lace_lux_aggr_map_entry_t")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LuxAggressiveness {
+    pub lux: u32,
+    pub aggressiveness_percent: u8,
+}
+
+impl From<ctl_lace_lux_aggr_map_entry_t> for LuxAggressiveness {
+    fn from(entry: ctl_lace_lux_aggr_map_entry_t) -> Self {
+        Self {
+            lux: entry.Lux,
+            aggressiveness_percent: entry.AggressivenessPercent,
+        }
+    }
+}
+
+impl From<LuxAggressiveness> for ctl_lace_lux_aggr_map_entry_t {
+    fn from(entry: LuxAggressiveness) -> Self {
+        Self {
+            Lux: entry.lux,
+            AggressivenessPercent: entry.aggressiveness_percent,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LaceMode {
+    /// Fixed aggressiveness level in percent.
+    #[doc(alias = "CTL_LACE_TRIGGER_FLAG_FIXED_AGGRESSIVENESS")]
+    FixedAggressiveness(u8),
+    /// Aggressiveness follows the ambient light, interpolated from entries sorted by lux.
+    #[doc(alias = "CTL_LACE_TRIGGER_FLAG_AMBIENT_LIGHT")]
+    AmbientAdaptive(Vec<LuxAggressiveness>),
+}
+
+/// Local adaptive contrast enhancement configuration.
+#[doc(alias = "ctl_lace_config_t")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LaceConfig {
+    pub enabled: bool,
+    pub mode: LaceMode,
+}
+
+impl LaceConfig {
+    pub fn trigger(&self) -> LaceTriggerFlags {
+        match self.mode {
+            LaceMode::FixedAggressiveness(_) => LaceTriggerFlags::FIXED_AGGRESSIVENESS,
+            LaceMode::AmbientAdaptive(_) => LaceTriggerFlags::AMBIENT_LIGHT,
+        }
+    }
+
+    /// Check the aggressiveness levels and the lux map, which may hold at most
+    /// `max_lux_map_entries` entries, see [`DisplayOutput::lace_max_lux_map_entries()`].
+    pub fn validate(&self, max_lux_map_entries: u32) -> Result<(), LaceConfigValidationError> {
+        match &self.mode {
+            &LaceMode::FixedAggressiveness(percent) => {
+                if percent > MAX_LACE_AGGRESSIVENESS {
+                    return Err(LaceConfigValidationError::AggressivenessOutOfRange {
+                        index: None,
+                        percent,
+                    });
+                }
+            }
+            LaceMode::AmbientAdaptive(map) => {
+                if map.is_empty() {
+                    return Err(LaceConfigValidationError::EmptyLuxMap);
+                }
+                if map.len() > max_lux_map_entries as usize {
+                    return Err(LaceConfigValidationError::TooManyEntries {
+                        max: max_lux_map_entries,
+                        found: map.len(),
+                    });
+                }
+
+                for (index, entry) in map.iter().enumerate() {
+                    if entry.aggressiveness_percent > MAX_LACE_AGGRESSIVENESS {
+                        return Err(LaceConfigValidationError::AggressivenessOutOfRange {
+                            index: Some(index),
+                            percent: entry.aggressiveness_percent,
+                        });
+                    }
+                    if index > 0 && entry.lux <= map[index - 1].lux {
+                        return Err(LaceConfigValidationError::LuxNotIncreasing { index });
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LaceConfigValidationError {
+    /// An aggressiveness level exceeds [`MAX_LACE_AGGRESSIVENESS`]. `index` is the lux map
+    /// entry, or [`None`] for a fixed level.
+    AggressivenessOutOfRange { index: Option<usize>, percent: u8 },
+    /// The lux map has no entries.
+    EmptyLuxMap,
+    /// The lux map has more entries than supported by the output.
+    TooManyEntries { max: u32, found: usize },
+    /// The lux value of an entry is not greater than the one of the previous entry.
+    LuxNotIncreasing { index: usize },
+}
+
+impl fmt::Display for LaceConfigValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AggressivenessOutOfRange {
+                index: Some(index),
+                percent,
+            } => write!(
+                f,
+                "aggressiveness {percent}% of lux map entry {index} exceeds {MAX_LACE_AGGRESSIVENESS}%"
+            ),
+            Self::AggressivenessOutOfRange {
+                index: None,
+                percent,
+            } => write!(
+                f,
+                "aggressiveness {percent}% exceeds {MAX_LACE_AGGRESSIVENESS}%"
+            ),
+            Self::EmptyLuxMap => f.write_str("lux map has no entries"),
+            Self::TooManyEntries { max, found } => write!(
+                f,
+                "lux map has {found} entries, but at most {max} are supported"
+            ),
+            Self::LuxNotIncreasing { index } => write!(
+                f,
+                "lux of map entry {index} is not greater than the one of the previous entry"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LaceConfigValidationError {}
+
+#[derive(Debug)]
+pub enum LaceError {
+    Invalid(LaceConfigValidationError),
+    /// The driver rejected the aggressiveness level or the lux map.
+    #[doc(alias = "CTL_RESULT_ERROR_LACE_INVALID_DATA_ARGUMENT_PASSED")]
+    InvalidData,
+    Igcl(Error),
+}
+
+impl fmt::Display for LaceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Invalid(error) => write!(f, "invalid LACE configuration: {error}"),
+            Self::InvalidData => fmt::Display::fmt(
+                &Error(ctl_result_t::CTL_RESULT_ERROR_LACE_INVALID_DATA_ARGUMENT_PASSED),
+                f,
+            ),
+            Self::Igcl(error) => fmt::Display::fmt(error, f),
+        }
+    }
+}
+
+impl std::error::Error for LaceError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Invalid(error) => Some(error),
+            Self::Igcl(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<LaceConfigValidationError> for LaceError {
+    fn from(error: LaceConfigValidationError) -> Self {
+        Self::Invalid(error)
+    }
+}
+
+impl From<Error> for LaceError {
+    fn from(error: Error) -> Self {
+        match error.0 {
+            ctl_result_t::CTL_RESULT_ERROR_LACE_INVALID_DATA_ARGUMENT_PASSED => Self::InvalidData,
+            _ => Self::Igcl(error),
+        }
+    }
+}
+
+impl DisplayOutput {
+    /// Maximum number of entries in the lux map of [`LaceMode::AmbientAdaptive`].
+    #[doc(alias = "CTL_GET_OPERATION_FLAG_CAPABILITY")]
+    pub fn lace_max_lux_map_entries(&self) -> Result<u32> {
+        let mut args = ctl_lace_config_t {
+            Size: std::mem::size_of::<ctl_lace_config_t>() as u32,
+            Version: 0,
+            OpTypeGet: ctl_get_operation_flag_t::CTL_GET_OPERATION_FLAG_CAPABILITY.0 as u32,
+            Trigger: LaceTriggerFlags::AMBIENT_LIGHT.bits(),
+            ..Default::default()
+        };
+
+        Error::from_result(unsafe {
+            self.control_lib
+                .ctlGetLACEConfig(self.display_output_handle, &mut args)
+        })?;
+
+        // SAFETY: The map is returned for the ambient light trigger
+        Ok(unsafe { args.LaceConfig.AggrLevelMap }.MaxNumEntries)
+    }
+
+    fn get_lace_config(&self, operation: ctl_get_operation_flag_t) -> Result<LaceConfig> {
+        let max_entries = self.lace_max_lux_map_entries()?;
+        let mut entries = vec![ctl_lace_lux_aggr_map_entry_t::default(); max_entries as usize];
+
+        let mut args = ctl_lace_config_t {
+            Size: std::mem::size_of::<ctl_lace_config_t>() as u32,
+            Version: 0,
+            OpTypeGet: operation.0 as u32,
+            Trigger: LaceTriggerFlags::AMBIENT_LIGHT.bits(),
+            LaceConfig: ctl_lace_aggr_config_t {
+                AggrLevelMap: ctl_lace_lux_aggr_map_t {
+                    MaxNumEntries: max_entries,
+                    NumEntries: max_entries,
+                    pLuxToAggrMappingTable: entries.as_mut_ptr(),
+                },
+            },
+            ..Default::default()
+        };
+
+        Error::from_result(unsafe {
+            self.control_lib
+                .ctlGetLACEConfig(self.display_output_handle, &mut args)
+        })?;
+
+        let trigger = LaceTriggerFlags::from_bits_retain(args.Trigger);
+        // SAFETY: The union variant is selected by the returned trigger
+        let mode = if trigger.contains(LaceTriggerFlags::AMBIENT_LIGHT) {
+            let map = unsafe { args.LaceConfig.AggrLevelMap };
+            entries.truncate(map.NumEntries.min(max_entries) as usize);
+            LaceMode::AmbientAdaptive(entries.into_iter().map(Into::into).collect())
+        } else {
+            LaceMode::FixedAggressiveness(unsafe {
+                args.LaceConfig.FixedAggressivenessLevelPercent
+            })
+        };
+
+        Ok(LaceConfig {
+            enabled: args.Enabled,
+            mode,
+        })
+    }
+
+    #[doc(alias = "ctlGetLACEConfig")]
+    #[doc(alias = "CTL_GET_OPERATION_FLAG_CURRENT")]
+    pub fn lace_config(&self) -> Result<LaceConfig> {
+        self.get_lace_config(ctl_get_operation_flag_t::CTL_GET_OPERATION_FLAG_CURRENT)
+    }
+
+    #[doc(alias = "CTL_GET_OPERATION_FLAG_DEFAULT")]
+    pub fn default_lace_config(&self) -> Result<LaceConfig> {
+        self.get_lace_config(ctl_get_operation_flag_t::CTL_GET_OPERATION_FLAG_DEFAULT)
+    }
+
+    /// Apply `config`, after validating it against [`Self::lace_max_lux_map_entries()`].
+    #[doc(alias = "ctlSetLACEConfig")]
+    #[doc(alias = "CTL_SET_OPERATION_CUSTOM")]
+    pub fn set_lace_config(&self, config: &LaceConfig) -> Result<(), LaceError> {
+        let mut entries = vec![];
+        let lace_config = match &config.mode {
+            &LaceMode::FixedAggressiveness(percent) => {
+                config.validate(0)?;
+                ctl_lace_aggr_config_t {
+                    FixedAggressivenessLevelPercent: percent,
+                }
+            }
+            LaceMode::AmbientAdaptive(map) => {
+                let max_entries = self.lace_max_lux_map_entries()?;
+                config.validate(max_entries)?;
+
+                entries.extend(
+                    map.iter()
+                        .map(|&entry| ctl_lace_lux_aggr_map_entry_t::from(entry)),
+                );
+                ctl_lace_aggr_config_t {
+                    AggrLevelMap: ctl_lace_lux_aggr_map_t {
+                        MaxNumEntries: max_entries,
+                        NumEntries: entries.len() as u32,
+                        pLuxToAggrMappingTable: entries.as_mut_ptr(),
+                    },
+                }
+            }
+        };
+
+        let mut args = ctl_lace_config_t {
+            Size: std::mem::size_of::<ctl_lace_config_t>() as u32,
+            Version: 0,
+            Enabled: config.enabled,
+            OpTypeGet: 0,
+            OpTypeSet: ctl_set_operation_t::CTL_SET_OPERATION_CUSTOM,
+            Trigger: config.trigger().bits(),
+            LaceConfig: lace_config,
+        };
+
+        Error::from_result(unsafe {
+            self.control_lib
+                .ctlSetLACEConfig(self.display_output_handle, &mut args)
+        })?;
+
+        Ok(())
+    }
+
+    /// Restore the driver's default LACE configuration.
+    #[doc(alias = "CTL_SET_OPERATION_RESTORE_DEFAULT")]
+    pub fn restore_default_lace_config(&self) -> Result<()> {
+        let mut args = ctl_lace_config_t {
+            Size: std::mem::size_of::<ctl_lace_config_t>() as u32,
+            Version: 0,
+            OpTypeSet: ctl_set_operation_t::CTL_SET_OPERATION_RESTORE_DEFAULT,
+            ..Default::default()
+        };
+
+        Error::from_result(unsafe {
+            self.control_lib
+                .ctlSetLACEConfig(self.display_output_handle, &mut args)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ambient(map: &[(u32, u8)]) -> LaceConfig {
+        LaceConfig {
+            enabled: true,
+            mode: LaceMode::AmbientAdaptive(
+                map.iter()
+                    .map(|&(lux, aggressiveness_percent)| LuxAggressiveness {
+                        lux,
+                        aggressiveness_percent,
+                    })
+                    .collect(),
+            ),
+        }
+    }
+
+    #[test]
+    fn fixed_aggressiveness() {
+        let fixed = |percent| LaceConfig {
+            enabled: true,
+            mode: LaceMode::FixedAggressiveness(percent),
+        };
+
+        assert_eq!(fixed(0).validate(0), Ok(()));
+        assert_eq!(fixed(MAX_LACE_AGGRESSIVENESS).validate(0), Ok(()));
+        assert_eq!(
+            fixed(MAX_LACE_AGGRESSIVENESS + 1).validate(0),
+            Err(LaceConfigValidationError::AggressivenessOutOfRange {
+                index: None,
+                percent: MAX_LACE_AGGRESSIVENESS + 1
+            })
+        );
+        assert_eq!(fixed(50).trigger(), LaceTriggerFlags::FIXED_AGGRESSIVENESS);
+    }
+
+    #[test]
+    fn ambient_adaptive() {
+        let config = ambient(&[(0, 0), (100, 20), (1000, 100)]);
+        assert_eq!(config.validate(3), Ok(()));
+        assert_eq!(config.trigger(), LaceTriggerFlags::AMBIENT_LIGHT);
+        assert_eq!(
+            config.validate(2),
+            Err(LaceConfigValidationError::TooManyEntries { max: 2, found: 3 })
+        );
+    }
+
+    #[test]
+    fn ambient_adaptive_invalid() {
+        assert_eq!(
+            ambient(&[]).validate(8),
+            Err(LaceConfigValidationError::EmptyLuxMap)
+        );
+        assert_eq!(
+            ambient(&[(0, 0), (100, 101)]).validate(8),
+            Err(LaceConfigValidationError::AggressivenessOutOfRange {
+                index: Some(1),
+                percent: 101
+            })
+        );
+        assert_eq!(
+            ambient(&[(0, 0), (100, 20), (100, 30)]).validate(8),
+            Err(LaceConfigValidationError::LuxNotIncreasing { index: 2 })
+        );
+        assert_eq!(
+            ambient(&[(500, 0), (100, 20)]).validate(8),
+            Err(LaceConfigValidationError::LuxNotIncreasing { index: 1 })
+        );
+    }
+}
@@ -0,0 +1,1312 @@
+use std::fmt;
+
+use crate::{
+    display::DisplayOutput,
+    error::{Error, Result},
+    ffi::{
+        ctl_pixtx_1dlut_config_t, ctl_pixtx_3dlut_config_t, ctl_pixtx_3dlut_sample_t,
+        ctl_pixtx_block_config_t, ctl_pixtx_block_type_t, ctl_pixtx_config_opertaion_type_t,
+        ctl_pixtx_config_query_type_t, ctl_pixtx_config_t, ctl_pixtx_lut_sampling_type_t,
+        ctl_pixtx_matrix_config_t, ctl_pixtx_pipe_get_config_t, ctl_pixtx_pipe_set_config_flag_t,
+        ctl_pixtx_pipe_set_config_t, ctl_result_t,
+    },
+};
+
+pub mod lut_file;
+
+#[doc(alias = "ctl_pixtx_block_type_t")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PixelBlockType {
+    OneDLut,
+    ThreeDLut,
+    Matrix3x3,
+    Matrix3x3AndOffsets,
+    /// A block type not known to these bindings.
+    Unknown(i32),
+}
+
+impl From<ctl_pixtx_block_type_t> for PixelBlockType {
+    fn from(block_type: ctl_pixtx_block_type_t) -> Self {
+        match block_type {
+            ctl_pixtx_block_type_t::CTL_PIXTX_BLOCK_TYPE_1D_LUT => Self::OneDLut,
+            ctl_pixtx_block_type_t::CTL_PIXTX_BLOCK_TYPE_3D_LUT => Self::ThreeDLut,
+            ctl_pixtx_block_type_t::CTL_PIXTX_BLOCK_TYPE_3X3_MATRIX => Self::Matrix3x3,
+            ctl_pixtx_block_type_t::CTL_PIXTX_BLOCK_TYPE_3X3_MATRIX_AND_OFFSETS => {
+                Self::Matrix3x3AndOffsets
+            }
+            ctl_pixtx_block_type_t(x) => Self::Unknown(x),
+        }
+    }
+}
+
+impl From<PixelBlockType> for ctl_pixtx_block_type_t {
+    fn from(block_type: PixelBlockType) -> Self {
+        match block_type {
+            PixelBlockType::OneDLut => Self::CTL_PIXTX_BLOCK_TYPE_1D_LUT,
+            PixelBlockType::ThreeDLut => Self::CTL_PIXTX_BLOCK_TYPE_3D_LUT,
+            PixelBlockType::Matrix3x3 => Self::CTL_PIXTX_BLOCK_TYPE_3X3_MATRIX,
+            PixelBlockType::Matrix3x3AndOffsets => {
+                Self::CTL_PIXTX_BLOCK_TYPE_3X3_MATRIX_AND_OFFSETS
+            }
+            PixelBlockType::Unknown(x) => Self(x),
+        }
+    }
+}
+
+#[doc(alias = "ctl_pixtx_lut_sampling_type_t")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LutSampling {
+    Uniform,
+    /// Samples must be taken at the positions reported in [`PixelBlockCaps::OneDLut`].
+    NonUniform,
+    /// A sampling type not known to these bindings.
+    Unknown(i32),
+}
+
+impl From<ctl_pixtx_lut_sampling_type_t> for LutSampling {
+    fn from(sampling: ctl_pixtx_lut_sampling_type_t) -> Self {
+        match sampling {
+            ctl_pixtx_lut_sampling_type_t::CTL_PIXTX_LUT_SAMPLING_TYPE_UNIFORM => Self::Uniform,
+            ctl_pixtx_lut_sampling_type_t::CTL_PIXTX_LUT_SAMPLING_TYPE_NONUNIFORM => {
+                Self::NonUniform
+            }
+            ctl_pixtx_lut_sampling_type_t(x) => Self::Unknown(x),
+        }
+    }
+}
+
+impl From<LutSampling> for ctl_pixtx_lut_sampling_type_t {
+    fn from(sampling: LutSampling) -> Self {
+        match sampling {
+            LutSampling::Uniform => Self::CTL_PIXTX_LUT_SAMPLING_TYPE_UNIFORM,
+            LutSampling::NonUniform => Self::CTL_PIXTX_LUT_SAMPLING_TYPE_NONUNIFORM,
+            LutSampling::Unknown(x) => Self(x),
+        }
+    }
+}
+
+/// Capabilities of a single block in the pixel transformation pipeline.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PixelBlockCaps {
+    OneDLut {
+        sampling: LutSampling,
+        num_samples_per_channel: u32,
+        num_channels: u32,
+        /// Sampling positions shared by all channels, only reported for non-uniformly
+        /// sampled LUTs.
+        sample_positions: Option<Vec<f64>>,
+    },
+    ThreeDLut {
+        num_samples_per_channel: u32,
+    },
+    Matrix {
+        with_offsets: bool,
+    },
+    /// A block type not known to these bindings.
+    Unknown(i32),
+}
+
+impl PixelBlockCaps {
+    pub fn block_type(&self) -> PixelBlockType {
+        match self {
+            Self::OneDLut { .. } => PixelBlockType::OneDLut,
+            Self::ThreeDLut { .. } => PixelBlockType::ThreeDLut,
+            Self::Matrix {
+                with_offsets: false,
+            } => PixelBlockType::Matrix3x3,
+            Self::Matrix { with_offsets: true } => PixelBlockType::Matrix3x3AndOffsets,
+            Self::Unknown(x) => PixelBlockType::Unknown(*x),
+        }
+    }
+}
+
+#[doc(alias = "ctl_pixtx_block_config_t")]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PixelBlock {
+    /// Unique ID of the block, fixed for a platform.
+    pub id: u32,
+    pub caps: PixelBlockCaps,
+}
+
+impl PixelBlock {
+    pub fn block_type(&self) -> PixelBlockType {
+        self.caps.block_type()
+    }
+}
+
+/// The pixel transformation blocks of a display pipe, in processing order.
+#[doc(alias = "ctl_pixtx_pipe_get_config_t")]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PixelPipeline {
+    pub blocks: Vec<PixelBlock>,
+}
+
+impl PixelPipeline {
+    pub fn block(&self, id: u32) -> Option<&PixelBlock> {
+        self.blocks.iter().find(|b| b.id == id)
+    }
+
+    /// Check that `config` can be applied to the block with ID `block_id`, returning that
+    /// block.
+    pub fn validate(
+        &self,
+        block_id: u32,
+        config: &PixelBlockConfig,
+    ) -> Result<&PixelBlock, PixelConfigValidationError> {
+        let block = self
+            .block(block_id)
+            .ok_or(PixelConfigValidationError::InvalidBlockId(block_id))?;
+        config.validate(block)?;
+        Ok(block)
+    }
+
+    /// Blocks of the given type, in processing order.
+    pub fn blocks_of_type(&self, block_type: PixelBlockType) -> impl Iterator<Item = &PixelBlock> {
+        self.blocks
+            .iter()
+            .filter(move |b| b.block_type() == block_type)
+    }
+}
+
+/// One dimensional per-channel lookup table with sample values in `0.0..=1.0`.
+#[doc(alias = "ctl_pixtx_1dlut_config_t")]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OneDLut {
+    /// `1` for a grey scale LUT that is applied to all channels, `3` for an RGB LUT.
+    pub num_channels: u32,
+    /// All samples of the red channel, followed by those of green and blue for RGB LUTs.
+    pub samples: Vec<f64>,
+}
+
+impl OneDLut {
+    pub fn grey(samples: Vec<f64>) -> Self {
+        Self {
+            num_channels: 1,
+            samples,
+        }
+    }
+
+    /// Returns [`None`] if the channels differ in length.
+    pub fn rgb(red: &[f64], green: &[f64], blue: &[f64]) -> Option<Self> {
+        if red.len() != green.len() || red.len() != blue.len() {
+            return None;
+        }
+
+        Some(Self {
+            num_channels: 3,
+            samples: [red, green, blue].concat(),
+        })
+    }
+
+    pub fn num_samples_per_channel(&self) -> usize {
+        match self.num_channels {
+            0 => 0,
+            n => self.samples.len() / n as usize,
+        }
+    }
+
+    pub fn channel(&self, channel: usize) -> Option<&[f64]> {
+        let len = self.num_samples_per_channel();
+        self.samples.get(channel * len..(channel + 1) * len)
+    }
+
+    /// Linearly interpolate `channel` at input position `x` in `0.0..=1.0`, assuming uniform
+    /// sampling. Grey scale LUTs return the same value for every channel.
+    pub fn eval(&self, channel: usize, x: f64) -> f64 {
+        let channel = if self.num_channels == 1 { 0 } else { channel };
+        self.channel(channel)
+            .map_or(x, |samples| interpolate(samples, x))
+    }
+
+    /// Resample every channel to `num_samples_per_channel` uniformly spaced samples.
+    pub fn resample(&self, num_samples_per_channel: usize) -> Self {
+        let positions: Vec<f64> = (0..num_samples_per_channel)
+            .map(|i| i as f64 / num_samples_per_channel.saturating_sub(1).max(1) as f64)
+            .collect();
+        self.resample_at(&positions)
+    }
+
+    /// Resample every channel at the given input positions in `0.0..=1.0`, as reported for
+    /// non-uniformly sampled blocks.
+    pub fn resample_at(&self, positions: &[f64]) -> Self {
+        Self {
+            num_channels: self.num_channels,
+            samples: (0..self.num_channels as usize)
+                .flat_map(|channel| positions.iter().map(move |&x| self.eval(channel, x)))
+                .collect(),
+        }
+    }
+
+    /// Resample this LUT to match the capabilities of a 1D LUT block. Grey scale LUTs are
+    /// expanded when the block requires more channels. Returns [`None`] for other block
+    /// types, if the channel counts differ otherwise, or if the samples cannot be split
+    /// evenly into channels.
+    pub fn fit_to(&self, caps: &PixelBlockCaps) -> Option<Self> {
+        let PixelBlockCaps::OneDLut {
+            num_samples_per_channel,
+            num_channels,
+            sample_positions,
+            ..
+        } = caps
+        else {
+            return None;
+        };
+
+        if self.samples.is_empty()
+            || self.num_samples_per_channel() * self.num_channels as usize != self.samples.len()
+            || (self.num_channels != 1 && self.num_channels != *num_channels)
+        {
+            return None;
+        }
+
+        let lut = if self.num_channels == 1 && *num_channels > 1 {
+            let grey = self.channel(0)?;
+            Self {
+                num_channels: *num_channels,
+                samples: grey.repeat(*num_channels as usize),
+            }
+        } else {
+            self.clone()
+        };
+
+        Some(match sample_positions {
+            Some(positions) => lut.resample_at(positions),
+            None => lut.resample(*num_samples_per_channel as usize),
+        })
+    }
+}
+
+/// Linearly interpolate uniformly spaced `samples` at `x` in `0.0..=1.0`.
+fn interpolate(samples: &[f64], x: f64) -> f64 {
+    match samples {
+        [] => x,
+        [value] => *value,
+        _ => {
+            let pos = x.clamp(0.0, 1.0) * (samples.len() - 1) as f64;
+            let i = (pos.floor() as usize).min(samples.len() - 2);
+            let t = pos - i as f64;
+            samples[i] + (samples[i + 1] - samples[i]) * t
+        }
+    }
+}
+
+/// Color space conversion matrix, applied as `post + matrix * (pixel + pre)`.
+#[doc(alias = "ctl_pixtx_matrix_config_t")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Matrix3x3 {
+    /// Row-major coefficients.
+    pub matrix: [[f64; 3]; 3],
+    pub pre_offsets: [f64; 3],
+    pub post_offsets: [f64; 3],
+}
+
+impl Matrix3x3 {
+    pub const IDENTITY: Self = Self::new([[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]);
+
+    pub const fn new(matrix: [[f64; 3]; 3]) -> Self {
+        Self {
+            matrix,
+            pre_offsets: [0.0; 3],
+            post_offsets: [0.0; 3],
+        }
+    }
+
+    pub fn has_offsets(&self) -> bool {
+        self.pre_offsets != [0.0; 3] || self.post_offsets != [0.0; 3]
+    }
+}
+
+impl From<ctl_pixtx_matrix_config_t> for Matrix3x3 {
+    fn from(config: ctl_pixtx_matrix_config_t) -> Self {
+        Self {
+            matrix: config.Matrix,
+            pre_offsets: config.PreOffsets,
+            post_offsets: config.PostOffsets,
+        }
+    }
+}
+
+impl From<Matrix3x3> for ctl_pixtx_matrix_config_t {
+    fn from(matrix: Matrix3x3) -> Self {
+        Self {
+            Size: std::mem::size_of::<ctl_pixtx_matrix_config_t>() as u32,
+            Version: 0,
+            PreOffsets: matrix.pre_offsets,
+            PostOffsets: matrix.post_offsets,
+            Matrix: matrix.matrix,
+        }
+    }
+}
+
+/// Three dimensional lookup table mapping an RGB input to an RGB output.
+#[doc(alias = "ctl_pixtx_3dlut_config_t")]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ThreeDLut {
+    pub num_samples_per_channel: u32,
+    /// `num_samples_per_channel³` RGB samples, with red in the outermost and blue in the
+    /// innermost loop.
+    pub samples: Vec<[f64; 3]>,
+}
+
+impl ThreeDLut {
+    /// A LUT that maps every input to itself.
+    pub fn identity(num_samples_per_channel: u32) -> Self {
+        let n = num_samples_per_channel as usize;
+        let scale = n.saturating_sub(1).max(1) as f64;
+        let mut samples = Vec::with_capacity(n * n * n);
+        for r in 0..n {
+            for g in 0..n {
+                for b in 0..n {
+                    samples.push([r as f64 / scale, g as f64 / scale, b as f64 / scale]);
+                }
+            }
+        }
+
+        Self {
+            num_samples_per_channel,
+            samples,
+        }
+    }
+
+    pub fn index(&self, r: usize, g: usize, b: usize) -> usize {
+        let n = self.num_samples_per_channel as usize;
+        (r * n + g) * n + b
+    }
+
+    pub fn sample(&self, r: usize, g: usize, b: usize) -> Option<&[f64; 3]> {
+        self.samples.get(self.index(r, g, b))
+    }
+
+    /// Trilinearly interpolate the LUT at `rgb`, with every component in `0.0..=1.0`.
+    pub fn eval(&self, rgb: [f64; 3]) -> [f64; 3] {
+        let n = self.num_samples_per_channel as usize;
+        if n < 2 || self.samples.len() != n * n * n {
+            return self.samples.first().copied().unwrap_or(rgb);
+        }
+
+        let mut index = [0; 3];
+        let mut t = [0.0; 3];
+        for c in 0..3 {
+            let pos = rgb[c].clamp(0.0, 1.0) * (n - 1) as f64;
+            index[c] = (pos.floor() as usize).min(n - 2);
+            t[c] = pos - index[c] as f64;
+        }
+
+        let mut out = [0.0; 3];
+        for corner in 0..8 {
+            let offset = [(corner >> 2) & 1, (corner >> 1) & 1, corner & 1];
+            let weight: f64 = (0..3)
+                .map(|c| if offset[c] == 1 { t[c] } else { 1.0 - t[c] })
+                .product();
+            let sample = self.samples[self.index(
+                index[0] + offset[0],
+                index[1] + offset[1],
+                index[2] + offset[2],
+            )];
+            for c in 0..3 {
+                out[c] += sample[c] * weight;
+            }
+        }
+        out
+    }
+
+    /// Resample to a LUT with `num_samples_per_channel` samples along every axis.
+    pub fn resample(&self, num_samples_per_channel: u32) -> Self {
+        let mut lut = Self::identity(num_samples_per_channel);
+        for sample in &mut lut.samples {
+            *sample = self.eval(*sample);
+        }
+        lut
+    }
+
+    /// Resample this LUT to match the capabilities of a 3D LUT block, returning [`None`] for
+    /// other block types or if the LUT does not hold `num_samples_per_channel³` samples.
+    pub fn fit_to(&self, caps: &PixelBlockCaps) -> Option<Self> {
+        let n = self.num_samples_per_channel as usize;
+        match caps {
+            PixelBlockCaps::ThreeDLut {
+                num_samples_per_channel,
+            } if self.samples.len() == n * n * n => Some(self.resample(*num_samples_per_channel)),
+            _ => None,
+        }
+    }
+}
+
+/// Configuration of a single pixel transformation block.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PixelBlockConfig {
+    OneDLut(OneDLut),
+    Matrix(Matrix3x3),
+    ThreeDLut(ThreeDLut),
+}
+
+/// Reasons for which a configuration is rejected before it is sent to the driver.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PixelConfigValidationError {
+    /// The pipeline has no block with this ID.
+    #[doc(alias = "CTL_RESULT_ERROR_INVALID_PIXTX_BLOCK_ID")]
+    InvalidBlockId(u32),
+    /// The configuration does not match the type of the block.
+    BlockTypeMismatch {
+        block_id: u32,
+        block_type: PixelBlockType,
+    },
+    /// A 1D LUT has a channel count other than `1` or the channel count of the block.
+    InvalidChannelCount { block_id: u32, num_channels: u32 },
+    /// The number of samples does not match the capabilities of the block.
+    #[doc(alias = "CTL_RESULT_ERROR_INVALID_SET_CONFIG_NUMBER_OF_SAMPLES")]
+    InvalidSampleCount {
+        block_id: u32,
+        expected: usize,
+        found: usize,
+    },
+    /// Offsets were given for a matrix block that does not support them.
+    OffsetsNotSupported { block_id: u32 },
+    /// A non-uniformly sampled 1D LUT block whose sample positions were not reported by
+    /// the driver cannot be configured.
+    UnknownSamplePositions { block_id: u32 },
+}
+
+impl fmt::Display for PixelConfigValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidBlockId(block_id) => write!(f, "no pixel block with id {block_id}"),
+            Self::BlockTypeMismatch {
+                block_id,
+                block_type,
+            } => write!(
+                f,
+                "configuration does not match pixel block {block_id} of type {block_type:?}"
+            ),
+            Self::InvalidChannelCount {
+                block_id,
+                num_channels,
+            } => write!(
+                f,
+                "{num_channels} channels are not supported by pixel block {block_id}"
+            ),
+            Self::InvalidSampleCount {
+                block_id,
+                expected,
+                found,
+            } => write!(
+                f,
+                "pixel block {block_id} expects {expected} samples, but {found} were given"
+            ),
+            Self::OffsetsNotSupported { block_id } => {
+                write!(f, "pixel block {block_id} does not support offsets")
+            }
+            Self::UnknownSamplePositions { block_id } => {
+                write!(f, "sample positions of pixel block {block_id} are unknown")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PixelConfigValidationError {}
+
+#[derive(Debug)]
+pub enum PixelPipelineError {
+    Invalid(PixelConfigValidationError),
+    /// The configuration cannot be persisted across power events.
+    #[doc(alias = "CTL_RESULT_ERROR_PERSISTANCE_NOT_SUPPORTED")]
+    PersistenceNotSupported,
+    Igcl(Error),
+}
+
+impl fmt::Display for PixelPipelineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Invalid(error) => write!(f, "invalid pixel block configuration: {error}"),
+            Self::PersistenceNotSupported => fmt::Display::fmt(
+                &Error(ctl_result_t::CTL_RESULT_ERROR_PERSISTANCE_NOT_SUPPORTED),
+                f,
+            ),
+            Self::Igcl(error) => fmt::Display::fmt(error, f),
+        }
+    }
+}
+
+impl std::error::Error for PixelPipelineError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Invalid(error) => Some(error),
+            Self::Igcl(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<PixelConfigValidationError> for PixelPipelineError {
+    fn from(error: PixelConfigValidationError) -> Self {
+        Self::Invalid(error)
+    }
+}
+
+impl From<Error> for PixelPipelineError {
+    fn from(error: Error) -> Self {
+        match error.0 {
+            ctl_result_t::CTL_RESULT_ERROR_PERSISTANCE_NOT_SUPPORTED => {
+                Self::PersistenceNotSupported
+            }
+            _ => Self::Igcl(error),
+        }
+    }
+}
+
+impl PixelBlockConfig {
+    /// Check that this configuration can be applied to `block`.
+    pub fn validate(&self, block: &PixelBlock) -> Result<(), PixelConfigValidationError> {
+        let block_id = block.id;
+        let mismatch = PixelConfigValidationError::BlockTypeMismatch {
+            block_id,
+            block_type: block.block_type(),
+        };
+
+        match (self, &block.caps) {
+            (
+                Self::OneDLut(lut),
+                PixelBlockCaps::OneDLut {
+                    sampling,
+                    num_samples_per_channel,
+                    num_channels,
+                    sample_positions,
+                },
+            ) => {
+                if *sampling == LutSampling::NonUniform && sample_positions.is_none() {
+                    return Err(PixelConfigValidationError::UnknownSamplePositions { block_id });
+                }
+
+                if lut.num_channels != 1 && lut.num_channels != *num_channels {
+                    return Err(PixelConfigValidationError::InvalidChannelCount {
+                        block_id,
+                        num_channels: lut.num_channels,
+                    });
+                }
+
+                let per_channel = lut.num_samples_per_channel();
+                let expected = *num_samples_per_channel as usize;
+                if per_channel != expected
+                    || lut.samples.len() != per_channel * lut.num_channels as usize
+                {
+                    return Err(PixelConfigValidationError::InvalidSampleCount {
+                        block_id,
+                        expected: expected * lut.num_channels as usize,
+                        found: lut.samples.len(),
+                    });
+                }
+            }
+            (Self::Matrix(matrix), PixelBlockCaps::Matrix { with_offsets }) => {
+                if !with_offsets && matrix.has_offsets() {
+                    return Err(PixelConfigValidationError::OffsetsNotSupported { block_id });
+                }
+            }
+            (
+                Self::ThreeDLut(lut),
+                PixelBlockCaps::ThreeDLut {
+                    num_samples_per_channel,
+                },
+            ) => {
+                let n = *num_samples_per_channel as usize;
+                if lut.num_samples_per_channel != *num_samples_per_channel
+                    || lut.samples.len() != n * n * n
+                {
+                    return Err(PixelConfigValidationError::InvalidSampleCount {
+                        block_id,
+                        expected: n * n * n,
+                        found: lut.samples.len(),
+                    });
+                }
+            }
+            _ => return Err(mismatch),
+        }
+
+        Ok(())
+    }
+}
+
+/// Owned buffers of a block configuration, which the pointers in `config` refer to.
+struct RawBlockConfig {
+    config: ctl_pixtx_block_config_t,
+    samples: Vec<f64>,
+    sample_positions: Vec<f64>,
+    samples_3d: Vec<ctl_pixtx_3dlut_sample_t>,
+}
+
+impl RawBlockConfig {
+    fn new(block_id: u32, block_type: PixelBlockType) -> Self {
+        Self {
+            config: ctl_pixtx_block_config_t {
+                Size: std::mem::size_of::<ctl_pixtx_block_config_t>() as u32,
+                Version: 0,
+                BlockId: block_id,
+                BlockType: block_type.into(),
+                Config: ctl_pixtx_config_t::default(),
+            },
+            samples: vec![],
+            sample_positions: vec![],
+            samples_3d: vec![],
+        }
+    }
+
+    fn from_config(block: &PixelBlock, config: &PixelBlockConfig) -> Self {
+        let mut raw = Self::new(block.id, block.block_type());
+
+        match config {
+            PixelBlockConfig::OneDLut(lut) => {
+                raw.samples = lut.samples.clone();
+                let sampling = match &block.caps {
+                    PixelBlockCaps::OneDLut {
+                        sampling,
+                        sample_positions,
+                        ..
+                    } => {
+                        raw.sample_positions = sample_positions.clone().unwrap_or_default();
+                        *sampling
+                    }
+                    _ => LutSampling::Uniform,
+                };
+                raw.config.Config.OneDLutConfig = ctl_pixtx_1dlut_config_t {
+                    Size: std::mem::size_of::<ctl_pixtx_1dlut_config_t>() as u32,
+                    Version: 0,
+                    SamplingType: sampling.into(),
+                    NumSamplesPerChannel: lut.num_samples_per_channel() as u32,
+                    NumChannels: lut.num_channels,
+                    pSampleValues: raw.samples.as_mut_ptr(),
+                    pSamplePositions: if raw.sample_positions.is_empty() {
+                        std::ptr::null_mut()
+                    } else {
+                        raw.sample_positions.as_mut_ptr()
+                    },
+                };
+            }
+            PixelBlockConfig::Matrix(matrix) => {
+                raw.config.Config.MatrixConfig = (*matrix).into();
+            }
+            PixelBlockConfig::ThreeDLut(lut) => {
+                raw.samples_3d = lut
+                    .samples
+                    .iter()
+                    .map(|&[r, g, b]| ctl_pixtx_3dlut_sample_t {
+                        Red: r,
+                        Green: g,
+                        Blue: b,
+                    })
+                    .collect();
+                raw.config.Config.ThreeDLutConfig = ctl_pixtx_3dlut_config_t {
+                    Size: std::mem::size_of::<ctl_pixtx_3dlut_config_t>() as u32,
+                    Version: 0,
+                    NumSamplesPerChannel: lut.num_samples_per_channel,
+                    pSampleValues: raw.samples_3d.as_mut_ptr(),
+                };
+            }
+        }
+
+        raw
+    }
+
+    /// Allocate buffers large enough to receive the current configuration of `block`.
+    fn for_query(block: &PixelBlock) -> Self {
+        let mut raw = Self::new(block.id, block.block_type());
+
+        match &block.caps {
+            PixelBlockCaps::OneDLut {
+                sampling,
+                num_samples_per_channel,
+                num_channels,
+                ..
+            } => {
+                raw.samples = vec![0.0; (*num_samples_per_channel * *num_channels) as usize];
+                raw.config.Config.OneDLutConfig = ctl_pixtx_1dlut_config_t {
+                    Size: std::mem::size_of::<ctl_pixtx_1dlut_config_t>() as u32,
+                    Version: 0,
+                    SamplingType: (*sampling).into(),
+                    NumSamplesPerChannel: *num_samples_per_channel,
+                    NumChannels: *num_channels,
+                    pSampleValues: raw.samples.as_mut_ptr(),
+                    pSamplePositions: std::ptr::null_mut(),
+                };
+            }
+            PixelBlockCaps::ThreeDLut {
+                num_samples_per_channel,
+            } => {
+                let n = *num_samples_per_channel as usize;
+                raw.samples_3d = vec![ctl_pixtx_3dlut_sample_t::default(); n * n * n];
+                raw.config.Config.ThreeDLutConfig = ctl_pixtx_3dlut_config_t {
+                    Size: std::mem::size_of::<ctl_pixtx_3dlut_config_t>() as u32,
+                    Version: 0,
+                    NumSamplesPerChannel: *num_samples_per_channel,
+                    pSampleValues: raw.samples_3d.as_mut_ptr(),
+                };
+            }
+            PixelBlockCaps::Matrix { .. } => {
+                raw.config.Config.MatrixConfig = Matrix3x3::IDENTITY.into();
+            }
+            PixelBlockCaps::Unknown(_) => {}
+        }
+
+        raw
+    }
+
+    fn into_config(self) -> Option<PixelBlockConfig> {
+        let block_type = PixelBlockType::from(self.config.BlockType);
+
+        // SAFETY: The union variant is selected by the block type
+        Some(match block_type {
+            PixelBlockType::OneDLut => {
+                let config = unsafe { self.config.Config.OneDLutConfig };
+                let len = (config.NumSamplesPerChannel * config.NumChannels) as usize;
+                let mut samples = self.samples;
+                samples.truncate(len);
+                PixelBlockConfig::OneDLut(OneDLut {
+                    num_channels: config.NumChannels,
+                    samples,
+                })
+            }
+            PixelBlockType::ThreeDLut => {
+                let config = unsafe { self.config.Config.ThreeDLutConfig };
+                let n = config.NumSamplesPerChannel as usize;
+                let mut samples = self.samples_3d;
+                samples.truncate(n * n * n);
+                PixelBlockConfig::ThreeDLut(ThreeDLut {
+                    num_samples_per_channel: config.NumSamplesPerChannel,
+                    samples: samples
+                        .into_iter()
+                        .map(|s| [s.Red, s.Green, s.Blue])
+                        .collect(),
+                })
+            }
+            PixelBlockType::Matrix3x3 | PixelBlockType::Matrix3x3AndOffsets => {
+                PixelBlockConfig::Matrix(unsafe { self.config.Config.MatrixConfig }.into())
+            }
+            PixelBlockType::Unknown(_) => return None,
+        })
+    }
+}
+
+/// The 1D LUT configuration of `config`, if it is a non-uniformly sampled 1D LUT.
+fn non_uniform_lut(config: &mut ctl_pixtx_block_config_t) -> Option<&mut ctl_pixtx_1dlut_config_t> {
+    if PixelBlockType::from(config.BlockType) != PixelBlockType::OneDLut {
+        return None;
+    }
+
+    // SAFETY: The union variant is selected by the block type
+    let lut = unsafe { &mut config.Config.OneDLutConfig };
+    (LutSampling::from(lut.SamplingType) == LutSampling::NonUniform).then_some(lut)
+}
+
+/// `sample_positions` is the buffer the positions of a non-uniformly sampled 1D LUT were
+/// queried into, if any.
+fn block_caps(
+    config: &ctl_pixtx_block_config_t,
+    sample_positions: Option<Vec<f64>>,
+) -> PixelBlockCaps {
+    // SAFETY: The union variant is selected by the block type
+    match PixelBlockType::from(config.BlockType) {
+        PixelBlockType::OneDLut => {
+            let lut = unsafe { config.Config.OneDLutConfig };
+            let sampling = LutSampling::from(lut.SamplingType);
+
+            PixelBlockCaps::OneDLut {
+                sampling,
+                num_samples_per_channel: lut.NumSamplesPerChannel,
+                num_channels: lut.NumChannels,
+                // Positions the driver did not fill in are left as NaN
+                sample_positions: sample_positions
+                    .filter(|positions| positions.iter().all(|p| !p.is_nan())),
+            }
+        }
+        PixelBlockType::ThreeDLut => PixelBlockCaps::ThreeDLut {
+            num_samples_per_channel: unsafe { config.Config.ThreeDLutConfig }.NumSamplesPerChannel,
+        },
+        PixelBlockType::Matrix3x3 => PixelBlockCaps::Matrix {
+            with_offsets: false,
+        },
+        PixelBlockType::Matrix3x3AndOffsets => PixelBlockCaps::Matrix { with_offsets: true },
+        PixelBlockType::Unknown(x) => PixelBlockCaps::Unknown(x),
+    }
+}
+
+impl DisplayOutput {
+    fn pixel_transformation_get_config(
+        &self,
+        query_type: ctl_pixtx_config_query_type_t,
+        blocks: &mut [ctl_pixtx_block_config_t],
+    ) -> Result<u32> {
+        let mut args = ctl_pixtx_pipe_get_config_t {
+            Size: std::mem::size_of::<ctl_pixtx_pipe_get_config_t>() as u32,
+            Version: 0,
+            QueryType: query_type,
+            NumBlocks: blocks.len() as u32,
+            pBlockConfigs: if blocks.is_empty() {
+                std::ptr::null_mut()
+            } else {
+                blocks.as_mut_ptr()
+            },
+            ..Default::default()
+        };
+
+        Error::from_result(unsafe {
+            self.control_lib
+                .ctlPixelTransformationGetConfig(self.display_output_handle, &mut args)
+        })?;
+
+        Ok(args.NumBlocks)
+    }
+
+    fn pixel_transformation_set_config(
+        &self,
+        operation: ctl_pixtx_config_opertaion_type_t,
+        persist: bool,
+        blocks: &mut [RawBlockConfig],
+    ) -> Result<(), PixelPipelineError> {
+        let mut configs: Vec<ctl_pixtx_block_config_t> = blocks.iter().map(|b| b.config).collect();
+
+        let mut args = ctl_pixtx_pipe_set_config_t {
+            Size: std::mem::size_of::<ctl_pixtx_pipe_set_config_t>() as u32,
+            Version: 0,
+            OpertaionType: operation,
+            Flags: if persist {
+                ctl_pixtx_pipe_set_config_flag_t::CTL_PIXTX_PIPE_SET_CONFIG_FLAG_PERSIST_ACROSS_POWER_EVENTS.0
+                    as u32
+            } else {
+                0
+            },
+            NumBlocks: configs.len() as u32,
+            pBlockConfigs: configs.as_mut_ptr(),
+        };
+
+        Error::from_result(unsafe {
+            self.control_lib
+                .ctlPixelTransformationSetConfig(self.display_output_handle, &mut args)
+        })?;
+
+        Ok(())
+    }
+
+    /// Query the pixel transformation blocks of this output and their capabilities.
+    #[doc(alias = "ctlPixelTransformationGetConfig")]
+    #[doc(alias = "CTL_PIXTX_CONFIG_QUERY_TYPE_CAPABILITY")]
+    pub fn pixel_pipeline(&self) -> Result<PixelPipeline> {
+        let query_type = ctl_pixtx_config_query_type_t::CTL_PIXTX_CONFIG_QUERY_TYPE_CAPABILITY;
+
+        let num_blocks = self.pixel_transformation_get_config(query_type, &mut [])?;
+
+        let mut blocks = vec![
+            ctl_pixtx_block_config_t {
+                Size: std::mem::size_of::<ctl_pixtx_block_config_t>() as u32,
+                ..Default::default()
+            };
+            num_blocks as usize
+        ];
+        let num_blocks = self.pixel_transformation_get_config(query_type, &mut blocks)?;
+        blocks.truncate(num_blocks as usize);
+
+        // The sample positions of non-uniformly sampled 1D LUTs are only known once the
+        // sample counts are, so they are queried into buffers owned here in a second pass.
+        let mut positions: Vec<Option<Vec<f64>>> = blocks
+            .iter_mut()
+            .map(|config| {
+                let lut = non_uniform_lut(config)?;
+                let mut positions = vec![f64::NAN; lut.NumSamplesPerChannel as usize];
+                lut.pSamplePositions = positions.as_mut_ptr();
+                Some(positions)
+            })
+            .collect();
+        if positions.iter().any(Option::is_some) {
+            self.pixel_transformation_get_config(query_type, &mut blocks)?;
+        }
+
+        Ok(PixelPipeline {
+            blocks: blocks
+                .iter()
+                .zip(positions.iter_mut())
+                .map(|(config, positions)| PixelBlock {
+                    id: config.BlockId,
+                    caps: block_caps(config, positions.take()),
+                })
+                .collect(),
+        })
+    }
+
+    /// Query the configuration currently applied to `block`, or [`None`] if the block type
+    /// is not known to these bindings.
+    #[doc(alias = "CTL_PIXTX_CONFIG_QUERY_TYPE_CURRENT")]
+    pub fn pixel_block_config(&self, block: &PixelBlock) -> Result<Option<PixelBlockConfig>> {
+        let mut raw = RawBlockConfig::for_query(block);
+        self.pixel_transformation_get_config(
+            ctl_pixtx_config_query_type_t::CTL_PIXTX_CONFIG_QUERY_TYPE_CURRENT,
+            std::slice::from_mut(&mut raw.config),
+        )?;
+
+        Ok(raw.into_config())
+    }
+
+    /// Apply `configs`, keyed by block ID, after validating them against the capabilities
+    /// in `pipeline`. With `persist`, the configuration survives power events.
+    #[doc(alias = "ctlPixelTransformationSetConfig")]
+    #[doc(alias = "CTL_PIXTX_CONFIG_OPERTAION_TYPE_SET_CUSTOM")]
+    pub fn set_pixel_blocks(
+        &self,
+        pipeline: &PixelPipeline,
+        configs: &[(u32, PixelBlockConfig)],
+        persist: bool,
+    ) -> Result<(), PixelPipelineError> {
+        let mut blocks = configs
+            .iter()
+            .map(|(block_id, config)| {
+                let block = pipeline.validate(*block_id, config)?;
+                Ok(RawBlockConfig::from_config(block, config))
+            })
+            .collect::<Result<Vec<_>, PixelConfigValidationError>>()?;
+
+        if blocks.is_empty() {
+            return Ok(());
+        }
+
+        self.pixel_transformation_set_config(
+            ctl_pixtx_config_opertaion_type_t::CTL_PIXTX_CONFIG_OPERTAION_TYPE_SET_CUSTOM,
+            persist,
+            &mut blocks,
+        )
+    }
+
+    /// Restore the driver's default configuration of `blocks`.
+    #[doc(alias = "CTL_PIXTX_CONFIG_OPERTAION_TYPE_RESTORE_DEFAULT")]
+    pub fn restore_default_pixel_blocks(
+        &self,
+        blocks: &[&PixelBlock],
+    ) -> Result<(), PixelPipelineError> {
+        if blocks.is_empty() {
+            return Ok(());
+        }
+
+        let mut blocks: Vec<RawBlockConfig> = blocks
+            .iter()
+            .map(|block| RawBlockConfig::new(block.id, block.block_type()))
+            .collect();
+        self.pixel_transformation_set_config(
+            ctl_pixtx_config_opertaion_type_t::CTL_PIXTX_CONFIG_OPERTAION_TYPE_RESTORE_DEFAULT,
+            false,
+            &mut blocks,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn one_d_block(num_samples_per_channel: u32, num_channels: u32) -> PixelBlock {
+        PixelBlock {
+            id: 1,
+            caps: PixelBlockCaps::OneDLut {
+                sampling: LutSampling::Uniform,
+                num_samples_per_channel,
+                num_channels,
+                sample_positions: None,
+            },
+        }
+    }
+
+    fn three_d_block(num_samples_per_channel: u32) -> PixelBlock {
+        PixelBlock {
+            id: 2,
+            caps: PixelBlockCaps::ThreeDLut {
+                num_samples_per_channel,
+            },
+        }
+    }
+
+    fn assert_close(a: &[f64], b: &[f64]) {
+        assert_eq!(a.len(), b.len(), "{a:?} != {b:?}");
+        assert!(
+            a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-12),
+            "{a:?} != {b:?}"
+        );
+    }
+
+    #[test]
+    fn one_d_eval() {
+        let lut = OneDLut::rgb(&[0.0, 0.5, 1.0], &[1.0, 0.5, 0.0], &[0.2, 0.2, 0.4]).unwrap();
+        assert_eq!(lut.eval(0, 0.0), 0.0);
+        assert_eq!(lut.eval(0, 1.0), 1.0);
+        assert_eq!(lut.eval(0, 0.25), 0.25);
+        assert_eq!(lut.eval(1, 0.5), 0.5);
+        assert_eq!(lut.eval(1, 0.75), 0.25);
+        assert_eq!(lut.eval(2, 0.0), 0.2);
+        assert_close(&[lut.eval(2, 0.75)], &[0.3]);
+        assert_eq!(lut.eval(2, 1.0), 0.4);
+        // Out of range inputs are clamped
+        assert_eq!(lut.eval(0, -1.0), 0.0);
+        assert_eq!(lut.eval(0, 2.0), 1.0);
+
+        let grey = OneDLut::grey(vec![0.0, 1.0]);
+        assert_eq!(grey.eval(0, 0.5), 0.5);
+        assert_eq!(grey.eval(2, 0.5), 0.5);
+    }
+
+    #[test]
+    fn one_d_resample() {
+        let identity = OneDLut::grey(vec![0.0, 1.0]);
+        assert_close(&identity.resample(5).samples, &[0.0, 0.25, 0.5, 0.75, 1.0]);
+
+        let lut = OneDLut::rgb(&[0.0, 1.0], &[1.0, 0.0], &[0.0, 0.5]).unwrap();
+        let resampled = lut.resample(3);
+        assert_eq!(resampled.num_channels, 3);
+        assert_close(
+            &resampled.samples,
+            &[0.0, 0.5, 1.0, 1.0, 0.5, 0.0, 0.0, 0.25, 0.5],
+        );
+
+        assert_close(
+            &identity.resample_at(&[0.0, 0.1, 0.9, 1.0]).samples,
+            &[0.0, 0.1, 0.9, 1.0],
+        );
+    }
+
+    #[test]
+    fn one_d_fit_to() {
+        let grey = OneDLut::grey(vec![0.0, 1.0]);
+        let lut = grey.fit_to(&one_d_block(3, 3).caps).unwrap();
+        assert_eq!(lut.num_channels, 3);
+        assert_close(&lut.samples, &[0.0, 0.5, 1.0, 0.0, 0.5, 1.0, 0.0, 0.5, 1.0]);
+        assert_eq!(
+            PixelBlockConfig::OneDLut(lut).validate(&one_d_block(3, 3)),
+            Ok(())
+        );
+
+        let non_uniform = PixelBlockCaps::OneDLut {
+            sampling: LutSampling::NonUniform,
+            num_samples_per_channel: 3,
+            num_channels: 1,
+            sample_positions: Some(vec![0.0, 0.25, 1.0]),
+        };
+        assert_close(
+            &grey.fit_to(&non_uniform).unwrap().samples,
+            &[0.0, 0.25, 1.0],
+        );
+
+        // Channel count mismatch
+        let rgb = OneDLut::rgb(&[0.0, 1.0], &[0.0, 1.0], &[0.0, 1.0]).unwrap();
+        assert_eq!(rgb.fit_to(&one_d_block(3, 1).caps), None);
+        // Sample count mismatch
+        let uneven = OneDLut {
+            num_channels: 3,
+            samples: vec![0.0, 1.0, 0.0, 1.0],
+        };
+        assert_eq!(uneven.fit_to(&one_d_block(3, 3).caps), None);
+        assert_eq!(OneDLut::grey(vec![]).fit_to(&one_d_block(3, 3).caps), None);
+        // Block type mismatch
+        assert_eq!(grey.fit_to(&three_d_block(3).caps), None);
+    }
+
+    #[test]
+    fn three_d_eval() {
+        let identity = ThreeDLut::identity(3);
+        for rgb in [
+            [0.0, 0.0, 0.0],
+            [1.0, 1.0, 1.0],
+            [0.25, 0.5, 0.75],
+            [0.1, 0.9, 0.3],
+        ] {
+            assert_close(&identity.eval(rgb), &rgb);
+        }
+
+        // Swap red and blue
+        let mut lut = ThreeDLut::identity(2);
+        for sample in &mut lut.samples {
+            sample.swap(0, 2);
+        }
+        assert_eq!(lut.eval([1.0, 0.0, 0.0]), [0.0, 0.0, 1.0]);
+        assert_eq!(lut.eval([0.0, 1.0, 1.0]), [1.0, 1.0, 0.0]);
+        assert_close(&lut.eval([0.5, 0.25, 0.0]), &[0.0, 0.25, 0.5]);
+    }
+
+    #[test]
+    fn three_d_resample() {
+        let identity = ThreeDLut::identity(2);
+        let resampled = identity.resample(5);
+        assert_eq!(resampled.num_samples_per_channel, 5);
+        let expected = ThreeDLut::identity(5);
+        assert_close(&resampled.samples.concat(), &expected.samples.concat());
+    }
+
+    #[test]
+    fn three_d_fit_to() {
+        let lut = ThreeDLut::identity(2)
+            .fit_to(&three_d_block(3).caps)
+            .unwrap();
+        assert_close(
+            &lut.samples.concat(),
+            &ThreeDLut::identity(3).samples.concat(),
+        );
+
+        let mut truncated = ThreeDLut::identity(3);
+        truncated.samples.pop();
+        assert_eq!(truncated.fit_to(&three_d_block(3).caps), None);
+        assert_eq!(ThreeDLut::identity(2).fit_to(&one_d_block(3, 3).caps), None);
+    }
+
+    #[test]
+    fn validate() {
+        let grey = PixelBlockConfig::OneDLut(OneDLut::grey(vec![0.0, 0.5, 1.0]));
+        assert_eq!(grey.validate(&one_d_block(3, 3)), Ok(()));
+        let rgb = OneDLut::rgb(&[0.0, 1.0], &[0.0, 1.0], &[0.0, 1.0]).unwrap();
+        assert_eq!(
+            PixelBlockConfig::OneDLut(rgb).validate(&one_d_block(2, 3)),
+            Ok(())
+        );
+        assert_eq!(
+            PixelBlockConfig::ThreeDLut(ThreeDLut::identity(4)).validate(&three_d_block(4)),
+            Ok(())
+        );
+        let matrix = PixelBlock {
+            id: 3,
+            caps: PixelBlockCaps::Matrix {
+                with_offsets: false,
+            },
+        };
+        assert_eq!(
+            PixelBlockConfig::Matrix(Matrix3x3::IDENTITY).validate(&matrix),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn validate_block_type() {
+        let config = PixelBlockConfig::ThreeDLut(ThreeDLut::identity(2));
+        assert_eq!(
+            config.validate(&one_d_block(2, 3)),
+            Err(PixelConfigValidationError::BlockTypeMismatch {
+                block_id: 1,
+                block_type: PixelBlockType::OneDLut,
+            })
+        );
+
+        let unknown = PixelBlock {
+            id: 4,
+            caps: PixelBlockCaps::Unknown(42),
+        };
+        assert_eq!(
+            config.validate(&unknown),
+            Err(PixelConfigValidationError::BlockTypeMismatch {
+                block_id: 4,
+                block_type: PixelBlockType::Unknown(42),
+            })
+        );
+    }
+
+    #[test]
+    fn validate_block_id() {
+        let pipeline = PixelPipeline {
+            blocks: vec![one_d_block(2, 3), three_d_block(2)],
+        };
+        let config = PixelBlockConfig::ThreeDLut(ThreeDLut::identity(2));
+        assert_eq!(pipeline.validate(2, &config), Ok(&three_d_block(2)));
+        assert_eq!(
+            pipeline.validate(5, &config),
+            Err(PixelConfigValidationError::InvalidBlockId(5))
+        );
+        assert_eq!(
+            pipeline.validate(1, &config),
+            Err(PixelConfigValidationError::BlockTypeMismatch {
+                block_id: 1,
+                block_type: PixelBlockType::OneDLut,
+            })
+        );
+    }
+
+    #[test]
+    fn validate_sample_count() {
+        let grey = PixelBlockConfig::OneDLut(OneDLut::grey(vec![0.0, 0.5, 1.0]));
+        assert_eq!(
+            grey.validate(&one_d_block(4, 3)),
+            Err(PixelConfigValidationError::InvalidSampleCount {
+                block_id: 1,
+                expected: 4,
+                found: 3,
+            })
+        );
+
+        let config = PixelBlockConfig::ThreeDLut(ThreeDLut::identity(3));
+        assert_eq!(
+            config.validate(&three_d_block(4)),
+            Err(PixelConfigValidationError::InvalidSampleCount {
+                block_id: 2,
+                expected: 64,
+                found: 27,
+            })
+        );
+
+        let mut lut = ThreeDLut::identity(4);
+        lut.samples.pop();
+        assert_eq!(
+            PixelBlockConfig::ThreeDLut(lut).validate(&three_d_block(4)),
+            Err(PixelConfigValidationError::InvalidSampleCount {
+                block_id: 2,
+                expected: 64,
+                found: 63,
+            })
+        );
+    }
+
+    #[test]
+    fn validate_channel_count() {
+        let rgb = OneDLut::rgb(&[0.0, 1.0], &[0.0, 1.0], &[0.0, 1.0]).unwrap();
+        assert_eq!(
+            PixelBlockConfig::OneDLut(rgb).validate(&one_d_block(2, 1)),
+            Err(PixelConfigValidationError::InvalidChannelCount {
+                block_id: 1,
+                num_channels: 3,
+            })
+        );
+    }
+
+    #[test]
+    fn validate_sample_positions() {
+        let mut block = PixelBlock {
+            id: 1,
+            caps: PixelBlockCaps::OneDLut {
+                sampling: LutSampling::NonUniform,
+                num_samples_per_channel: 3,
+                num_channels: 1,
+                sample_positions: None,
+            },
+        };
+        let config = PixelBlockConfig::OneDLut(OneDLut::grey(vec![0.0, 0.25, 1.0]));
+        assert_eq!(
+            config.validate(&block),
+            Err(PixelConfigValidationError::UnknownSamplePositions { block_id: 1 })
+        );
+
+        if let PixelBlockCaps::OneDLut {
+            sample_positions, ..
+        } = &mut block.caps
+        {
+            *sample_positions = Some(vec![0.0, 0.5, 1.0]);
+        }
+        assert_eq!(config.validate(&block), Ok(()));
+
+        let raw = RawBlockConfig::from_config(&block, &config);
+        let lut = unsafe { raw.config.Config.OneDLutConfig };
+        assert_eq!(
+            lut.SamplingType,
+            ctl_pixtx_lut_sampling_type_t::CTL_PIXTX_LUT_SAMPLING_TYPE_NONUNIFORM
+        );
+        assert_eq!(
+            lut.pSamplePositions,
+            raw.sample_positions.as_ptr() as *mut f64
+        );
+        assert_eq!(raw.sample_positions, [0.0, 0.5, 1.0]);
+    }
+}
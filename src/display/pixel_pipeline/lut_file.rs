@@ -0,0 +1,614 @@
+use std::{fmt, fs, io, path::Path, str::FromStr};
+
+use crate::display::pixel_pipeline::{OneDLut, PixelBlockConfig, ThreeDLut};
+
+/// Largest 1D LUT size allowed by the `.cube` format.
+pub const MAX_CUBE_1D_SIZE: usize = 65536;
+
+/// Largest 3D LUT size allowed by the `.cube` format. IGCL has no fixed limit, the size a 3D
+/// LUT block supports is reported in its
+/// [`PixelBlockCaps`](crate::display::pixel_pipeline::PixelBlockCaps).
+pub const MAX_CUBE_3D_SIZE: usize = 256;
+
+#[derive(Debug)]
+pub enum LutFileError {
+    Io(io::Error),
+    /// A line could not be parsed.
+    InvalidLine {
+        line: usize,
+    },
+    /// A keyword that is not supported by this parser.
+    UnknownKeyword {
+        line: usize,
+        keyword: String,
+    },
+    /// A LUT size is below 2 or exceeds [`MAX_CUBE_1D_SIZE`] or [`MAX_CUBE_3D_SIZE`].
+    SizeOutOfRange {
+        line: usize,
+        size: usize,
+    },
+    /// Only LUTs with an input domain of `0.0..=1.0` are supported.
+    UnsupportedDomain {
+        line: usize,
+    },
+    /// The file contains no LUT data.
+    Empty,
+    /// The number of data lines does not match the declared LUT sizes.
+    SampleCountMismatch {
+        expected: usize,
+        found: usize,
+    },
+    /// Rows of a CSV table have different column counts, or a count other than 1 or 3.
+    InvalidColumnCount {
+        line: usize,
+        columns: usize,
+    },
+}
+
+impl fmt::Display for LutFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => fmt::Display::fmt(error, f),
+            Self::InvalidLine { line } => write!(f, "line {line} could not be parsed"),
+            Self::UnknownKeyword { line, keyword } => {
+                write!(f, "unknown keyword `{keyword}` on line {line}")
+            }
+            Self::SizeOutOfRange { line, size } => {
+                write!(f, "LUT size {size} on line {line} is out of range")
+            }
+            Self::UnsupportedDomain { line } => write!(
+                f,
+                "input domain on line {line} is not supported, only 0.0 to 1.0 is"
+            ),
+            Self::Empty => f.write_str("file contains no LUT data"),
+            Self::SampleCountMismatch { expected, found } => {
+                write!(f, "expected {expected} samples, but found {found}")
+            }
+            Self::InvalidColumnCount { line, columns } => {
+                write!(
+                    f,
+                    "line {line} has an unexpected number of columns ({columns})"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for LutFileError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for LutFileError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+/// Contents of a `.cube` file: a 1D LUT, a 3D LUT, or a 1D shaper LUT followed by a 3D LUT.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CubeLut {
+    pub title: Option<String>,
+    /// An RGB LUT when parsed; grey scale LUTs are written to all three channels.
+    pub one_d: Option<OneDLut>,
+    pub three_d: Option<ThreeDLut>,
+}
+
+fn parse_floats<const N: usize>(values: &[&str], line: usize) -> Result<[f64; N], LutFileError> {
+    let invalid = LutFileError::InvalidLine { line };
+    if values.len() != N {
+        return Err(invalid);
+    }
+
+    let mut out = [0.0; N];
+    for (out, value) in out.iter_mut().zip(values) {
+        *out = value
+            .parse()
+            .map_err(|_| LutFileError::InvalidLine { line })?;
+    }
+    Ok(out)
+}
+
+fn parse_size(values: &[&str], line: usize, max: usize) -> Result<usize, LutFileError> {
+    let size = match values {
+        [size] => size
+            .parse()
+            .map_err(|_| LutFileError::InvalidLine { line })?,
+        _ => return Err(LutFileError::InvalidLine { line }),
+    };
+    if !(2..=max).contains(&size) {
+        return Err(LutFileError::SizeOutOfRange { line, size });
+    }
+    Ok(size)
+}
+
+/// Remove a `#` comment from `line`, ignoring `#` inside quoted strings.
+fn strip_comment(line: &str) -> &str {
+    let mut quoted = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '#' if !quoted => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+impl FromStr for CubeLut {
+    type Err = LutFileError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut title = None;
+        let mut size_1d = 0;
+        let mut size_3d = 0;
+        let mut rows = vec![];
+
+        for (i, line) in s.lines().enumerate() {
+            let line_number = i + 1;
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let mut words = line.split_whitespace();
+            let keyword = words.next().unwrap_or_default();
+
+            if keyword
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_ascii_digit() || matches!(c, '-' | '+' | '.'))
+            {
+                let values: Vec<&str> = line.split_whitespace().collect();
+                rows.push(parse_floats::<3>(&values, line_number)?);
+                continue;
+            }
+
+            let values: Vec<&str> = words.collect();
+            match keyword {
+                "TITLE" => {
+                    let rest = line["TITLE".len()..].trim();
+                    title = Some(rest.trim_matches('"').to_owned());
+                }
+                "LUT_1D_SIZE" => size_1d = parse_size(&values, line_number, MAX_CUBE_1D_SIZE)?,
+                "LUT_3D_SIZE" => size_3d = parse_size(&values, line_number, MAX_CUBE_3D_SIZE)?,
+                "DOMAIN_MIN" | "DOMAIN_MAX" => {
+                    let expected = if keyword == "DOMAIN_MIN" { 0.0 } else { 1.0 };
+                    let domain = parse_floats::<3>(&values, line_number)?;
+                    if domain.iter().any(|&v| v != expected) {
+                        return Err(LutFileError::UnsupportedDomain { line: line_number });
+                    }
+                }
+                "LUT_1D_INPUT_RANGE" | "LUT_3D_INPUT_RANGE" => {
+                    if parse_floats::<2>(&values, line_number)? != [0.0, 1.0] {
+                        return Err(LutFileError::UnsupportedDomain { line: line_number });
+                    }
+                }
+                // Resolve flags describing the signal range the LUT is used with, which do
+                // not affect its contents
+                "LUT_IN_VIDEO_RANGE" | "LUT_OUT_VIDEO_RANGE" => {}
+                _ => {
+                    return Err(LutFileError::UnknownKeyword {
+                        line: line_number,
+                        keyword: keyword.to_owned(),
+                    })
+                }
+            }
+        }
+
+        if size_1d == 0 && size_3d == 0 {
+            return Err(LutFileError::Empty);
+        }
+
+        // Sizes are bounded above, but an overflow would never match the number of rows
+        let expected = size_3d
+            .checked_mul(size_3d)
+            .and_then(|n| n.checked_mul(size_3d))
+            .and_then(|n| n.checked_add(size_1d))
+            .unwrap_or(usize::MAX);
+        if rows.len() != expected {
+            return Err(LutFileError::SampleCountMismatch {
+                expected,
+                found: rows.len(),
+            });
+        }
+
+        // Shaper LUT data precedes the 3D LUT data
+        let (rows_1d, rows_3d) = rows.split_at(size_1d);
+
+        let one_d = (size_1d > 0).then(|| OneDLut {
+            num_channels: 3,
+            samples: (0..3)
+                .flat_map(|c| rows_1d.iter().map(move |row| row[c]))
+                .collect(),
+        });
+
+        // Red changes fastest in `.cube` files, whereas it is the outermost axis in IGCL
+        let three_d = (size_3d > 0).then(|| {
+            let mut lut = ThreeDLut {
+                num_samples_per_channel: size_3d as u32,
+                samples: vec![[0.0; 3]; rows_3d.len()],
+            };
+            for (i, row) in rows_3d.iter().enumerate() {
+                let (r, g, b) = (i % size_3d, i / size_3d % size_3d, i / (size_3d * size_3d));
+                let index = lut.index(r, g, b);
+                lut.samples[index] = *row;
+            }
+            lut
+        });
+
+        Ok(Self {
+            title,
+            one_d,
+            three_d,
+        })
+    }
+}
+
+impl fmt::Display for CubeLut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(title) = &self.title {
+            writeln!(f, "TITLE \"{title}\"")?;
+        }
+
+        let one_d = self.one_d.as_ref().map(|lut| {
+            let len = lut.num_samples_per_channel();
+            let channel = |c: usize| lut.channel(c).or_else(|| lut.channel(0)).unwrap_or(&[]);
+            (len, [channel(0), channel(1), channel(2)])
+        });
+
+        if let Some((len, _)) = one_d {
+            writeln!(f, "LUT_1D_SIZE {len}")?;
+        }
+        if let Some(lut) = &self.three_d {
+            writeln!(f, "LUT_3D_SIZE {}", lut.num_samples_per_channel)?;
+        }
+        writeln!(f)?;
+
+        if let Some((len, [r, g, b])) = one_d {
+            for i in 0..len {
+                writeln!(f, "{} {} {}", r[i], g[i], b[i])?;
+            }
+        }
+
+        if let Some(lut) = &self.three_d {
+            let n = lut.num_samples_per_channel as usize;
+            for b in 0..n {
+                for g in 0..n {
+                    for r in 0..n {
+                        let [x, y, z] = lut.sample(r, g, b).copied().unwrap_or_default();
+                        writeln!(f, "{x} {y} {z}")?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl CubeLut {
+    pub fn read(path: impl AsRef<Path>) -> Result<Self, LutFileError> {
+        fs::read_to_string(path)?.parse()
+    }
+
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<(), LutFileError> {
+        Ok(fs::write(path, self.to_string())?)
+    }
+
+    /// Wrap a LUT configuration, e.g. as read back through
+    /// [`DisplayOutput::pixel_block_config()`][crate::display::DisplayOutput::pixel_block_config],
+    /// for export. Returns [`None`] for matrix configurations.
+    pub fn from_block_config(config: &PixelBlockConfig) -> Option<Self> {
+        match config {
+            PixelBlockConfig::OneDLut(lut) => Some(Self {
+                one_d: Some(lut.clone()),
+                ..Default::default()
+            }),
+            PixelBlockConfig::ThreeDLut(lut) => Some(Self {
+                three_d: Some(lut.clone()),
+                ..Default::default()
+            }),
+            PixelBlockConfig::Matrix(_) => None,
+        }
+    }
+}
+
+/// Parse a gamma table with one row per uniformly spaced sample, and either one grey scale
+/// column or red, green and blue columns separated by commas. A non-numeric header row is
+/// skipped. Integer code values, e.g. `0..=1023`, are normalized to `0.0..=1.0` using the
+/// smallest `2^n - 1` that contains the largest value.
+pub fn parse_csv_gamma(s: &str) -> Result<OneDLut, LutFileError> {
+    let mut columns = 0;
+    let mut header_skipped = false;
+    let mut rows: Vec<Vec<f64>> = vec![];
+
+    for (i, line) in s.lines().enumerate() {
+        let line_number = i + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let values: Result<Vec<f64>, _> = line.split(',').map(|v| v.trim().parse()).collect();
+        let values = match values {
+            Ok(values) => values,
+            Err(_) if rows.is_empty() && !header_skipped => {
+                header_skipped = true;
+                continue;
+            }
+            Err(_) => return Err(LutFileError::InvalidLine { line: line_number }),
+        };
+
+        if columns == 0 {
+            columns = values.len();
+        }
+        if values.len() != columns || !matches!(columns, 1 | 3) {
+            return Err(LutFileError::InvalidColumnCount {
+                line: line_number,
+                columns: values.len(),
+            });
+        }
+        rows.push(values);
+    }
+
+    if rows.is_empty() {
+        return Err(LutFileError::Empty);
+    }
+
+    let max = rows.iter().flatten().fold(0.0f64, |max, &v| max.max(v));
+    let scale = if max > 1.0 {
+        2f64.powi((max + 1.0).log2().ceil() as i32) - 1.0
+    } else {
+        1.0
+    };
+
+    Ok(OneDLut {
+        num_channels: columns as u32,
+        samples: (0..columns)
+            .flat_map(|c| rows.iter().map(move |row| row[c] / scale))
+            .collect(),
+    })
+}
+
+pub fn read_csv_gamma(path: impl AsRef<Path>) -> Result<OneDLut, LutFileError> {
+    parse_csv_gamma(&fs::read_to_string(path)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shaper() -> OneDLut {
+        OneDLut::rgb(&[0.0, 0.25, 1.0], &[0.0, 0.5, 1.0], &[0.0, 0.75, 1.0]).unwrap()
+    }
+
+    fn three_d() -> ThreeDLut {
+        let mut lut = ThreeDLut::identity(3);
+        let index = lut.index(2, 1, 0);
+        lut.samples[index] = [0.125, 0.375, 0.625];
+        lut
+    }
+
+    #[test]
+    fn cube_round_trip() {
+        for lut in [
+            CubeLut {
+                title: Some("Shaper and cube".to_owned()),
+                one_d: Some(shaper()),
+                three_d: Some(three_d()),
+            },
+            CubeLut {
+                title: None,
+                one_d: Some(shaper()),
+                three_d: None,
+            },
+            CubeLut {
+                title: None,
+                one_d: None,
+                three_d: Some(three_d()),
+            },
+        ] {
+            assert_eq!(lut.to_string().parse::<CubeLut>().unwrap(), lut);
+        }
+    }
+
+    #[test]
+    fn cube_round_trip_is_exact() {
+        let third = 1.0 / 3.0;
+        let lut = CubeLut {
+            one_d: OneDLut::rgb(
+                &[0.0, third, 1.0],
+                &[0.0, 0.1 + 0.2, 1.0],
+                &[0.0, 1e-9, 1.0],
+            ),
+            three_d: Some(ThreeDLut::identity(4)),
+            ..Default::default()
+        };
+        assert_eq!(lut.to_string().parse::<CubeLut>().unwrap(), lut);
+    }
+
+    #[test]
+    fn cube_grey_scale_is_written_to_all_channels() {
+        let lut = CubeLut {
+            one_d: Some(OneDLut::grey(vec![0.0, 0.5, 1.0])),
+            ..Default::default()
+        };
+        let parsed: CubeLut = lut.to_string().parse().unwrap();
+        assert_eq!(
+            parsed.one_d,
+            OneDLut::rgb(&[0.0, 0.5, 1.0], &[0.0, 0.5, 1.0], &[0.0, 0.5, 1.0])
+        );
+    }
+
+    #[test]
+    fn cube_parse() {
+        let cube = "\
+# Created by hand
+TITLE \"Test\"
+LUT_3D_SIZE 2
+DOMAIN_MIN 0 0 0
+DOMAIN_MAX 1 1 1
+LUT_3D_INPUT_RANGE 0.0 1.0
+LUT_IN_VIDEO_RANGE
+LUT_OUT_VIDEO_RANGE
+
+0 0 0
+1 0 0 # red changes fastest
+0 1 0
+1 1 0
+0 0 1
+1 0 1
+0 1 1
+1 1 1
+";
+        let lut: CubeLut = cube.parse().unwrap();
+        assert_eq!(lut.title.as_deref(), Some("Test"));
+        assert_eq!(lut.one_d, None);
+        assert_eq!(lut.three_d, Some(ThreeDLut::identity(2)));
+    }
+
+    #[test]
+    fn cube_title_with_hash() {
+        let lut: CubeLut = "TITLE \"Grade #2\" # comment\nLUT_1D_SIZE 2\n0 0 0\n1 1 1\n"
+            .parse()
+            .unwrap();
+        assert_eq!(lut.title.as_deref(), Some("Grade #2"));
+
+        let lut = CubeLut {
+            title: Some("#1".to_owned()),
+            one_d: Some(shaper()),
+            three_d: None,
+        };
+        assert_eq!(lut.to_string().parse::<CubeLut>().unwrap(), lut);
+    }
+
+    #[test]
+    fn cube_malformed() {
+        let parse = |s: &str| s.parse::<CubeLut>().unwrap_err();
+
+        assert!(matches!(parse(""), LutFileError::Empty));
+        assert!(matches!(
+            parse("LUT_3D_SIZE 2\n0 0 0\n"),
+            LutFileError::SampleCountMismatch {
+                expected: 8,
+                found: 1
+            }
+        ));
+        assert!(matches!(
+            parse("LUT_1D_SIZE 2\n0 0\n1 1 1\n"),
+            LutFileError::InvalidLine { line: 2 }
+        ));
+        assert!(matches!(
+            parse("LUT_1D_SIZE 2\n0 0 zero\n1 1 1\n"),
+            LutFileError::InvalidLine { line: 2 }
+        ));
+        assert!(matches!(
+            parse("LUT_1D_SIZE two\n"),
+            LutFileError::InvalidLine { line: 1 }
+        ));
+        assert!(matches!(
+            parse("LUT_SIZE 2\n"),
+            LutFileError::UnknownKeyword { line: 1, ref keyword } if keyword == "LUT_SIZE"
+        ));
+        assert!(matches!(
+            parse("LUT_1D_SIZE 2\nDOMAIN_MAX 1 1 2\n"),
+            LutFileError::UnsupportedDomain { line: 2 }
+        ));
+        assert!(matches!(
+            parse("LUT_1D_INPUT_RANGE 0 4\n"),
+            LutFileError::UnsupportedDomain { line: 1 }
+        ));
+        assert!(matches!(
+            parse("LUT_3D_SIZE 257\n"),
+            LutFileError::SizeOutOfRange { line: 1, size: 257 }
+        ));
+        assert!(matches!(
+            parse("LUT_3D_SIZE 18446744073709551615\n"),
+            LutFileError::SizeOutOfRange { line: 1, .. } | LutFileError::InvalidLine { line: 1 }
+        ));
+        assert!(matches!(
+            parse("LUT_1D_SIZE 1\n0 0 0\n"),
+            LutFileError::SizeOutOfRange { line: 1, size: 1 }
+        ));
+    }
+
+    #[test]
+    fn csv_grey() {
+        let lut = parse_csv_gamma("value\n0.0\n0.5\n1.0\n").unwrap();
+        assert_eq!(lut, OneDLut::grey(vec![0.0, 0.5, 1.0]));
+    }
+
+    #[test]
+    fn csv_rgb_code_values() {
+        let csv = "# 10-bit gamma\nred, green, blue\n0, 0, 0\n341, 512, 682\n1023, 1023, 1023\n";
+        let lut = parse_csv_gamma(csv).unwrap();
+        assert_eq!(lut.num_channels, 3);
+        assert_eq!(lut.channel(0), Some(&[0.0, 341.0 / 1023.0, 1.0][..]));
+        assert_eq!(lut.channel(1), Some(&[0.0, 512.0 / 1023.0, 1.0][..]));
+        assert_eq!(lut.channel(2), Some(&[0.0, 682.0 / 1023.0, 1.0][..]));
+    }
+
+    #[test]
+    fn csv_small_code_values() {
+        // Any value above 1.0 means integer code values, even if it is below 2.0
+        let lut = parse_csv_gamma("0\n1\n1.5\n").unwrap();
+        assert_eq!(lut, OneDLut::grey(vec![0.0, 1.0 / 3.0, 0.5]));
+        let lut = parse_csv_gamma("0\n1\n2\n").unwrap();
+        assert_eq!(lut, OneDLut::grey(vec![0.0, 1.0 / 3.0, 2.0 / 3.0]));
+        let lut = parse_csv_gamma("0\n3\n").unwrap();
+        assert_eq!(lut, OneDLut::grey(vec![0.0, 1.0]));
+        let lut = parse_csv_gamma("0\n255\n256\n").unwrap();
+        assert_eq!(lut, OneDLut::grey(vec![0.0, 255.0 / 511.0, 256.0 / 511.0]));
+    }
+
+    #[test]
+    fn csv_round_trip_through_cube() {
+        let lut = parse_csv_gamma("0, 0, 0\n0.25, 0.5, 0.75\n1, 1, 1\n").unwrap();
+        assert_eq!(lut, shaper());
+
+        let cube = CubeLut {
+            one_d: Some(lut.clone()),
+            ..Default::default()
+        };
+        assert_eq!(
+            cube.to_string().parse::<CubeLut>().unwrap().one_d,
+            Some(lut)
+        );
+    }
+
+    #[test]
+    fn csv_malformed() {
+        assert!(matches!(
+            parse_csv_gamma("value\n"),
+            Err(LutFileError::Empty)
+        ));
+        assert!(matches!(
+            parse_csv_gamma("0\n0.5\nhalf\n"),
+            Err(LutFileError::InvalidLine { line: 3 })
+        ));
+        assert!(matches!(
+            parse_csv_gamma("header\nheader\n0\n"),
+            Err(LutFileError::InvalidLine { line: 2 })
+        ));
+        assert!(matches!(
+            parse_csv_gamma("0, 0\n1, 1\n"),
+            Err(LutFileError::InvalidColumnCount {
+                line: 1,
+                columns: 2
+            })
+        ));
+        assert!(matches!(
+            parse_csv_gamma("0, 0, 0\n1\n"),
+            Err(LutFileError::InvalidColumnCount {
+                line: 2,
+                columns: 1
+            })
+        ));
+    }
+}
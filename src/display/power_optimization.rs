@@ -0,0 +1,641 @@
+use std::fmt;
+
+use crate::{
+    display::DisplayOutput,
+    error::{Error, Result},
+    ffi::{
+        ctl_power_optimization_caps_t, ctl_power_optimization_dpst_flag_t,
+        ctl_power_optimization_dpst_t, ctl_power_optimization_feature_specific_info_t,
+        ctl_power_optimization_flag_t, ctl_power_optimization_lrr_flag_t,
+        ctl_power_optimization_lrr_t, ctl_power_optimization_plan_t, ctl_power_optimization_psr_t,
+        ctl_power_optimization_settings_t, ctl_power_source_t, ctl_result_t,
+    },
+};
+
+bitflags::bitflags! {
+    #[doc(alias = "ctl_power_optimization_flags_t")]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct PowerOptimizationFlags: u32 {
+        /// Frame buffer compression.
+        const FBC = ctl_power_optimization_flag_t::CTL_POWER_OPTIMIZATION_FLAG_FBC.0 as u32;
+        /// Panel self refresh.
+        const PSR = ctl_power_optimization_flag_t::CTL_POWER_OPTIMIZATION_FLAG_PSR.0 as u32;
+        /// Display power saving technology.
+        const DPST = ctl_power_optimization_flag_t::CTL_POWER_OPTIMIZATION_FLAG_DPST.0 as u32;
+        /// Low refresh rate.
+        const LRR = ctl_power_optimization_flag_t::CTL_POWER_OPTIMIZATION_FLAG_LRR.0 as u32;
+        /// Lighting aware contrast enhancement.
+        const LACE = ctl_power_optimization_flag_t::CTL_POWER_OPTIMIZATION_FLAG_LACE.0 as u32;
+    }
+}
+
+bitflags::bitflags! {
+    #[doc(alias = "ctl_power_optimization_dpst_flags_t")]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct DpstFlags: u32 {
+        /// Intel DPST with backlight control.
+        const BACKLIGHT = ctl_power_optimization_dpst_flag_t::CTL_POWER_OPTIMIZATION_DPST_FLAG_BKLT.0 as u32;
+        /// Panel TCON specific content adaptive backlight control.
+        const PANEL_CABC = ctl_power_optimization_dpst_flag_t::CTL_POWER_OPTIMIZATION_DPST_FLAG_PANEL_CABC.0 as u32;
+        /// Intel OLED power saving technology.
+        const OPST = ctl_power_optimization_dpst_flag_t::CTL_POWER_OPTIMIZATION_DPST_FLAG_OPST.0 as u32;
+        /// TCON based edge luminance profile.
+        const ELP = ctl_power_optimization_dpst_flag_t::CTL_POWER_OPTIMIZATION_DPST_FLAG_ELP.0 as u32;
+        /// Extra power saving mode.
+        const EPSM = ctl_power_optimization_dpst_flag_t::CTL_POWER_OPTIMIZATION_DPST_FLAG_EPSM.0 as u32;
+        /// Adaptive pixel dimming.
+        const APD = ctl_power_optimization_dpst_flag_t::CTL_POWER_OPTIMIZATION_DPST_FLAG_APD.0 as u32;
+        const PIXOPTIX = ctl_power_optimization_dpst_flag_t::CTL_POWER_OPTIMIZATION_DPST_FLAG_PIXOPTIX.0 as u32;
+    }
+}
+
+bitflags::bitflags! {
+    #[doc(alias = "ctl_power_optimization_lrr_flags_t")]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct LrrFlags: u32 {
+        const LRR_1_0 = ctl_power_optimization_lrr_flag_t::CTL_POWER_OPTIMIZATION_LRR_FLAG_LRR10.0 as u32;
+        const LRR_2_0 = ctl_power_optimization_lrr_flag_t::CTL_POWER_OPTIMIZATION_LRR_FLAG_LRR20.0 as u32;
+        const LRR_2_5 = ctl_power_optimization_lrr_flag_t::CTL_POWER_OPTIMIZATION_LRR_FLAG_LRR25.0 as u32;
+        /// Autonomous LRR.
+        const ALRR = ctl_power_optimization_lrr_flag_t::CTL_POWER_OPTIMIZATION_LRR_FLAG_ALRR.0 as u32;
+        /// User based low refresh rate, mutually exclusive with [`Self::UBZRR`].
+        const UBLRR = ctl_power_optimization_lrr_flag_t::CTL_POWER_OPTIMIZATION_LRR_FLAG_UBLRR.0 as u32;
+        /// User based zero refresh rate, mutually exclusive with [`Self::UBLRR`].
+        const UBZRR = ctl_power_optimization_lrr_flag_t::CTL_POWER_OPTIMIZATION_LRR_FLAG_UBZRR.0 as u32;
+    }
+}
+
+#[doc(alias = "ctl_power_source_t")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PowerSource {
+    Ac,
+    /// Battery power.
+    Dc,
+    /// A power source not known to these bindings.
+    Unknown(i32),
+}
+
+impl From<ctl_power_source_t> for PowerSource {
+    fn from(source: ctl_power_source_t) -> Self {
+        match source {
+            ctl_power_source_t::CTL_POWER_SOURCE_AC => Self::Ac,
+            ctl_power_source_t::CTL_POWER_SOURCE_DC => Self::Dc,
+            ctl_power_source_t(x) => Self::Unknown(x),
+        }
+    }
+}
+
+impl From<PowerSource> for ctl_power_source_t {
+    fn from(source: PowerSource) -> Self {
+        match source {
+            PowerSource::Ac => Self::CTL_POWER_SOURCE_AC,
+            PowerSource::Dc => Self::CTL_POWER_SOURCE_DC,
+            PowerSource::Unknown(x) => Self(x),
+        }
+    }
+}
+
+#[doc(alias = "ctl_power_optimization_plan_t")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PowerOptimizationPlan {
+    Balanced,
+    HighPerformance,
+    PowerSaver,
+    /// A power plan not known to these bindings.
+    Unknown(i32),
+}
+
+impl From<ctl_power_optimization_plan_t> for PowerOptimizationPlan {
+    fn from(plan: ctl_power_optimization_plan_t) -> Self {
+        match plan {
+            ctl_power_optimization_plan_t::CTL_POWER_OPTIMIZATION_PLAN_BALANCED => Self::Balanced,
+            ctl_power_optimization_plan_t::CTL_POWER_OPTIMIZATION_PLAN_HIGH_PERFORMANCE => {
+                Self::HighPerformance
+            }
+            ctl_power_optimization_plan_t::CTL_POWER_OPTIMIZATION_PLAN_POWER_SAVER => {
+                Self::PowerSaver
+            }
+            ctl_power_optimization_plan_t(x) => Self::Unknown(x),
+        }
+    }
+}
+
+impl From<PowerOptimizationPlan> for ctl_power_optimization_plan_t {
+    fn from(plan: PowerOptimizationPlan) -> Self {
+        match plan {
+            PowerOptimizationPlan::Balanced => Self::CTL_POWER_OPTIMIZATION_PLAN_BALANCED,
+            PowerOptimizationPlan::HighPerformance => {
+                Self::CTL_POWER_OPTIMIZATION_PLAN_HIGH_PERFORMANCE
+            }
+            PowerOptimizationPlan::PowerSaver => Self::CTL_POWER_OPTIMIZATION_PLAN_POWER_SAVER,
+            PowerOptimizationPlan::Unknown(x) => Self(x),
+        }
+    }
+}
+
+/// The power plan and power source a power optimization setting applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PowerProfile {
+    pub plan: PowerOptimizationPlan,
+    pub source: PowerSource,
+}
+
+impl PowerProfile {
+    pub fn new(plan: PowerOptimizationPlan, source: PowerSource) -> Self {
+        Self { plan, source }
+    }
+}
+
+/// Panel self refresh settings.
+#[doc(alias = "ctl_power_optimization_psr_t")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PsrSettings {
+    pub enable: bool,
+    /// 1 for PSR1, 2 for PSR2.
+    pub version: u8,
+    pub full_fetch_update: bool,
+}
+
+/// Display power saving technology settings.
+#[doc(alias = "ctl_power_optimization_dpst_t")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DpstSettings {
+    pub enable: bool,
+    /// Lowest supported aggressiveness level, ignored when setting.
+    pub min_level: u8,
+    /// Highest supported aggressiveness level, ignored when setting.
+    pub max_level: u8,
+    /// Aggressiveness level, within `min_level..=max_level`.
+    pub level: u8,
+    /// Ignored when setting.
+    pub supported_features: DpstFlags,
+    /// Exactly one of the supported features has to be given when enabling DPST.
+    pub enabled_features: DpstFlags,
+}
+
+impl DpstSettings {
+    /// Check that enabling DPST requests exactly one of the features supported in `current`,
+    /// as returned by [`DisplayOutput::dpst_settings()`], and a level within its range.
+    pub fn validate(&self, current: &DpstSettings) -> Result<(), PowerOptimizationError> {
+        if !self.enable {
+            return Ok(());
+        }
+        if self.enabled_features.bits().count_ones() != 1
+            || !current.supported_features.contains(self.enabled_features)
+        {
+            return Err(PowerOptimizationError::InvalidDpstFeatures(
+                self.enabled_features,
+            ));
+        }
+        if !(current.min_level..=current.max_level).contains(&self.level) {
+            return Err(PowerOptimizationError::DpstLevelOutOfRange {
+                level: self.level,
+                min: current.min_level,
+                max: current.max_level,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Low refresh rate settings.
+#[doc(alias = "ctl_power_optimization_lrr_t")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LrrSettings {
+    pub enable: bool,
+    /// Ignored when setting.
+    pub supported_types: LrrFlags,
+    pub current_types: LrrFlags,
+    /// PSR has to be disabled before changing the LRR types, and may be re-enabled
+    /// afterwards. Ignored when setting.
+    pub requires_psr_disable: bool,
+    /// Lowest refresh rate used by LRR, if known. Ignored when setting.
+    pub low_refresh_rate: Option<u16>,
+}
+
+impl LrrSettings {
+    /// Check that the mutually exclusive [`LrrFlags::UBLRR`] and [`LrrFlags::UBZRR`] types
+    /// are not requested together.
+    pub fn validate(&self) -> Result<(), PowerOptimizationError> {
+        if self
+            .current_types
+            .contains(LrrFlags::UBLRR | LrrFlags::UBZRR)
+        {
+            return Err(PowerOptimizationError::ConflictingLrrTypes(
+                self.current_types,
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub enum PowerOptimizationError {
+    /// The output does not support the power optimization feature.
+    #[doc(alias = "CTL_RESULT_ERROR_INVALID_POWERFEATURE_OPTIMIZATION_FLAG")]
+    UnsupportedFeature,
+    /// DPST can only be configured for the DC power source.
+    #[doc(alias = "CTL_RESULT_ERROR_INVALID_POWERSOURCE_TYPE_FOR_DPST")]
+    DpstRequiresDc,
+    /// DPST was enabled without requesting exactly one supported feature.
+    InvalidDpstFeatures(DpstFlags),
+    /// The DPST level is outside the range supported by the output.
+    DpstLevelOutOfRange {
+        level: u8,
+        min: u8,
+        max: u8,
+    },
+    /// Both [`LrrFlags::UBLRR`] and [`LrrFlags::UBZRR`] were requested.
+    ConflictingLrrTypes(LrrFlags),
+    /// FBC state cannot be changed on this output.
+    #[doc(alias = "CTL_RESULT_ERROR_SET_FBC_FEATURE_NOT_SUPPORTED")]
+    SetFbcNotSupported,
+    Igcl(Error),
+}
+
+impl fmt::Display for PowerOptimizationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedFeature => fmt::Display::fmt(
+                &Error(ctl_result_t::CTL_RESULT_ERROR_INVALID_POWERFEATURE_OPTIMIZATION_FLAG),
+                f,
+            ),
+            Self::DpstRequiresDc => fmt::Display::fmt(
+                &Error(ctl_result_t::CTL_RESULT_ERROR_INVALID_POWERSOURCE_TYPE_FOR_DPST),
+                f,
+            ),
+            Self::InvalidDpstFeatures(features) => write!(
+                f,
+                "exactly one supported DPST feature has to be enabled, got {features:?}"
+            ),
+            Self::DpstLevelOutOfRange { level, min, max } => {
+                write!(f, "DPST level {level} is not within {min}..={max}")
+            }
+            Self::ConflictingLrrTypes(types) => write!(
+                f,
+                "UBLRR and UBZRR cannot be enabled together, got {types:?}"
+            ),
+            Self::SetFbcNotSupported => fmt::Display::fmt(
+                &Error(ctl_result_t::CTL_RESULT_ERROR_SET_FBC_FEATURE_NOT_SUPPORTED),
+                f,
+            ),
+            Self::Igcl(error) => fmt::Display::fmt(error, f),
+        }
+    }
+}
+
+impl std::error::Error for PowerOptimizationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Igcl(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<Error> for PowerOptimizationError {
+    fn from(error: Error) -> Self {
+        match error.0 {
+            ctl_result_t::CTL_RESULT_ERROR_INVALID_POWERFEATURE_OPTIMIZATION_FLAG => {
+                Self::UnsupportedFeature
+            }
+            ctl_result_t::CTL_RESULT_ERROR_INVALID_POWERSOURCE_TYPE_FOR_DPST => {
+                Self::DpstRequiresDc
+            }
+            ctl_result_t::CTL_RESULT_ERROR_SET_FBC_FEATURE_NOT_SUPPORTED => {
+                Self::SetFbcNotSupported
+            }
+            _ => Self::Igcl(error),
+        }
+    }
+}
+
+impl DisplayOutput {
+    /// Query the power optimization features supported by this output.
+    #[doc(alias = "ctlGetPowerOptimizationCaps")]
+    pub fn power_optimization_caps(&self) -> Result<PowerOptimizationFlags> {
+        let mut caps = ctl_power_optimization_caps_t {
+            Size: std::mem::size_of::<ctl_power_optimization_caps_t>() as u32,
+            Version: 0,
+            ..Default::default()
+        };
+
+        Error::from_result(unsafe {
+            self.control_lib
+                .ctlGetPowerOptimizationCaps(self.display_output_handle, &mut caps)
+        })?;
+
+        Ok(PowerOptimizationFlags::from_bits_retain(
+            caps.SupportedFeatures,
+        ))
+    }
+
+    fn power_optimization_setting(
+        &self,
+        feature: PowerOptimizationFlags,
+        profile: PowerProfile,
+        data: ctl_power_optimization_feature_specific_info_t,
+    ) -> Result<ctl_power_optimization_settings_t, PowerOptimizationError> {
+        let mut args = ctl_power_optimization_settings_t {
+            Size: std::mem::size_of::<ctl_power_optimization_settings_t>() as u32,
+            Version: 0,
+            PowerOptimizationPlan: profile.plan.into(),
+            PowerOptimizationFeature: feature.bits(),
+            FeatureSpecificData: data,
+            PowerSource: profile.source.into(),
+            ..Default::default()
+        };
+
+        Error::from_result(unsafe {
+            self.control_lib
+                .ctlGetPowerOptimizationSetting(self.display_output_handle, &mut args)
+        })?;
+
+        Ok(args)
+    }
+
+    fn set_power_optimization_setting(
+        &self,
+        feature: PowerOptimizationFlags,
+        profile: PowerProfile,
+        enable: bool,
+        data: ctl_power_optimization_feature_specific_info_t,
+    ) -> Result<(), PowerOptimizationError> {
+        let mut args = ctl_power_optimization_settings_t {
+            Size: std::mem::size_of::<ctl_power_optimization_settings_t>() as u32,
+            Version: 0,
+            PowerOptimizationPlan: profile.plan.into(),
+            PowerOptimizationFeature: feature.bits(),
+            Enable: enable,
+            FeatureSpecificData: data,
+            PowerSource: profile.source.into(),
+        };
+
+        Error::from_result(unsafe {
+            self.control_lib
+                .ctlSetPowerOptimizationSetting(self.display_output_handle, &mut args)
+        })?;
+
+        Ok(())
+    }
+
+    #[doc(alias = "ctlGetPowerOptimizationSetting")]
+    #[doc(alias = "CTL_POWER_OPTIMIZATION_FLAG_PSR")]
+    pub fn psr_settings(
+        &self,
+        profile: PowerProfile,
+    ) -> Result<PsrSettings, PowerOptimizationError> {
+        let data = ctl_power_optimization_feature_specific_info_t {
+            PSRInfo: ctl_power_optimization_psr_t {
+                Size: std::mem::size_of::<ctl_power_optimization_psr_t>() as u32,
+                ..Default::default()
+            },
+        };
+        let args = self.power_optimization_setting(PowerOptimizationFlags::PSR, profile, data)?;
+
+        // SAFETY: The union variant is selected by the requested feature
+        let psr = unsafe { args.FeatureSpecificData.PSRInfo };
+        Ok(PsrSettings {
+            enable: args.Enable,
+            version: psr.PSRVersion,
+            full_fetch_update: psr.FullFetchUpdate,
+        })
+    }
+
+    #[doc(alias = "ctlSetPowerOptimizationSetting")]
+    pub fn set_psr_settings(
+        &self,
+        profile: PowerProfile,
+        settings: &PsrSettings,
+    ) -> Result<(), PowerOptimizationError> {
+        let data = ctl_power_optimization_feature_specific_info_t {
+            PSRInfo: ctl_power_optimization_psr_t {
+                Size: std::mem::size_of::<ctl_power_optimization_psr_t>() as u32,
+                Version: 0,
+                PSRVersion: settings.version,
+                FullFetchUpdate: settings.full_fetch_update,
+            },
+        };
+        self.set_power_optimization_setting(
+            PowerOptimizationFlags::PSR,
+            profile,
+            settings.enable,
+            data,
+        )
+    }
+
+    /// Query the DPST settings. DPST is only available for [`PowerSource::Dc`], which is
+    /// checked before calling the driver.
+    #[doc(alias = "CTL_POWER_OPTIMIZATION_FLAG_DPST")]
+    pub fn dpst_settings(
+        &self,
+        profile: PowerProfile,
+    ) -> Result<DpstSettings, PowerOptimizationError> {
+        if profile.source != PowerSource::Dc {
+            return Err(PowerOptimizationError::DpstRequiresDc);
+        }
+
+        let data = ctl_power_optimization_feature_specific_info_t {
+            DPSTInfo: ctl_power_optimization_dpst_t {
+                Size: std::mem::size_of::<ctl_power_optimization_dpst_t>() as u32,
+                ..Default::default()
+            },
+        };
+        let args = self.power_optimization_setting(PowerOptimizationFlags::DPST, profile, data)?;
+
+        // SAFETY: The union variant is selected by the requested feature
+        let dpst = unsafe { args.FeatureSpecificData.DPSTInfo };
+        Ok(DpstSettings {
+            enable: args.Enable,
+            min_level: dpst.MinLevel,
+            max_level: dpst.MaxLevel,
+            level: dpst.Level,
+            supported_features: DpstFlags::from_bits_retain(dpst.SupportedFeatures),
+            enabled_features: DpstFlags::from_bits_retain(dpst.EnabledFeatures),
+        })
+    }
+
+    /// Apply DPST settings, after checking that the profile targets [`PowerSource::Dc`] and
+    /// validating them with [`DpstSettings::validate()`] against the current settings.
+    pub fn set_dpst_settings(
+        &self,
+        profile: PowerProfile,
+        settings: &DpstSettings,
+    ) -> Result<(), PowerOptimizationError> {
+        // Also checks the power source
+        let current = self.dpst_settings(profile)?;
+        settings.validate(&current)?;
+
+        let data = ctl_power_optimization_feature_specific_info_t {
+            DPSTInfo: ctl_power_optimization_dpst_t {
+                Size: std::mem::size_of::<ctl_power_optimization_dpst_t>() as u32,
+                Version: 0,
+                Level: settings.level,
+                EnabledFeatures: settings.enabled_features.bits(),
+                ..Default::default()
+            },
+        };
+        self.set_power_optimization_setting(
+            PowerOptimizationFlags::DPST,
+            profile,
+            settings.enable,
+            data,
+        )
+    }
+
+    #[doc(alias = "CTL_POWER_OPTIMIZATION_FLAG_LRR")]
+    pub fn lrr_settings(
+        &self,
+        profile: PowerProfile,
+    ) -> Result<LrrSettings, PowerOptimizationError> {
+        let data = ctl_power_optimization_feature_specific_info_t {
+            LRRInfo: ctl_power_optimization_lrr_t {
+                Size: std::mem::size_of::<ctl_power_optimization_lrr_t>() as u32,
+                ..Default::default()
+            },
+        };
+        let args = self.power_optimization_setting(PowerOptimizationFlags::LRR, profile, data)?;
+
+        // SAFETY: The union variant is selected by the requested feature
+        let lrr = unsafe { args.FeatureSpecificData.LRRInfo };
+        Ok(LrrSettings {
+            enable: args.Enable,
+            supported_types: LrrFlags::from_bits_retain(lrr.SupportedLRRTypes),
+            current_types: LrrFlags::from_bits_retain(lrr.CurrentLRRTypes),
+            requires_psr_disable: lrr.bRequirePSRDisable,
+            low_refresh_rate: (lrr.LowRR != 0).then_some(lrr.LowRR),
+        })
+    }
+
+    /// Apply LRR settings, after validating them with [`LrrSettings::validate()`].
+    pub fn set_lrr_settings(
+        &self,
+        profile: PowerProfile,
+        settings: &LrrSettings,
+    ) -> Result<(), PowerOptimizationError> {
+        settings.validate()?;
+
+        let data = ctl_power_optimization_feature_specific_info_t {
+            LRRInfo: ctl_power_optimization_lrr_t {
+                Size: std::mem::size_of::<ctl_power_optimization_lrr_t>() as u32,
+                Version: 0,
+                CurrentLRRTypes: settings.current_types.bits(),
+                ..Default::default()
+            },
+        };
+        self.set_power_optimization_setting(
+            PowerOptimizationFlags::LRR,
+            profile,
+            settings.enable,
+            data,
+        )
+    }
+
+    /// Whether frame buffer compression is enabled.
+    #[doc(alias = "CTL_POWER_OPTIMIZATION_FLAG_FBC")]
+    pub fn fbc_enabled(&self, profile: PowerProfile) -> Result<bool, PowerOptimizationError> {
+        let args = self.power_optimization_setting(
+            PowerOptimizationFlags::FBC,
+            profile,
+            Default::default(),
+        )?;
+        Ok(args.Enable)
+    }
+
+    pub fn set_fbc_enabled(
+        &self,
+        profile: PowerProfile,
+        enable: bool,
+    ) -> Result<(), PowerOptimizationError> {
+        self.set_power_optimization_setting(
+            PowerOptimizationFlags::FBC,
+            profile,
+            enable,
+            Default::default(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dpst(enable: bool, level: u8, enabled_features: DpstFlags) -> DpstSettings {
+        DpstSettings {
+            enable,
+            min_level: 1,
+            max_level: 6,
+            level,
+            supported_features: DpstFlags::BACKLIGHT | DpstFlags::EPSM,
+            enabled_features,
+        }
+    }
+
+    #[test]
+    fn dpst_features() {
+        let current = dpst(false, 1, DpstFlags::empty());
+
+        assert!(dpst(true, 3, DpstFlags::BACKLIGHT)
+            .validate(&current)
+            .is_ok());
+        assert!(matches!(
+            dpst(true, 3, DpstFlags::empty()).validate(&current),
+            Err(PowerOptimizationError::InvalidDpstFeatures(features)) if features.is_empty()
+        ));
+        assert!(matches!(
+            dpst(true, 3, DpstFlags::BACKLIGHT | DpstFlags::EPSM).validate(&current),
+            Err(PowerOptimizationError::InvalidDpstFeatures(_))
+        ));
+        assert!(matches!(
+            dpst(true, 3, DpstFlags::OPST).validate(&current),
+            Err(PowerOptimizationError::InvalidDpstFeatures(features)) if features == DpstFlags::OPST
+        ));
+        assert!(dpst(false, 3, DpstFlags::empty())
+            .validate(&current)
+            .is_ok());
+    }
+
+    #[test]
+    fn dpst_level() {
+        let current = dpst(false, 1, DpstFlags::empty());
+
+        for level in [1, 6] {
+            assert!(dpst(true, level, DpstFlags::BACKLIGHT)
+                .validate(&current)
+                .is_ok());
+        }
+        for level in [0, 7] {
+            assert!(matches!(
+                dpst(true, level, DpstFlags::BACKLIGHT).validate(&current),
+                Err(PowerOptimizationError::DpstLevelOutOfRange { min: 1, max: 6, .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn lrr_types() {
+        let lrr = |current_types| LrrSettings {
+            enable: true,
+            supported_types: LrrFlags::all(),
+            current_types,
+            requires_psr_disable: false,
+            low_refresh_rate: None,
+        };
+
+        assert!(lrr(LrrFlags::UBLRR).validate().is_ok());
+        assert!(lrr(LrrFlags::UBZRR | LrrFlags::ALRR).validate().is_ok());
+        assert!(matches!(
+            lrr(LrrFlags::UBLRR | LrrFlags::UBZRR).validate(),
+            Err(PowerOptimizationError::ConflictingLrrTypes(_))
+        ));
+    }
+}
@@ -0,0 +1,497 @@
+use std::fmt;
+
+use crate::{
+    display::DisplayOutput,
+    error::{Error, Result},
+    ffi::{
+        ctl_display_setting_audio_t, ctl_display_setting_content_type_t,
+        ctl_display_setting_flag_t, ctl_display_setting_low_latency_t,
+        ctl_display_setting_picture_ar_flag_t, ctl_display_setting_quantization_range_t,
+        ctl_display_setting_sourcetm_t, ctl_display_settings_t,
+    },
+};
+
+bitflags::bitflags! {
+    #[doc(alias = "ctl_display_setting_flags_t")]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct DisplaySettingFlags: u32 {
+        const LOW_LATENCY = ctl_display_setting_flag_t::CTL_DISPLAY_SETTING_FLAG_LOW_LATENCY.0 as u32;
+        const SOURCE_TM = ctl_display_setting_flag_t::CTL_DISPLAY_SETTING_FLAG_SOURCE_TM.0 as u32;
+        const CONTENT_TYPE = ctl_display_setting_flag_t::CTL_DISPLAY_SETTING_FLAG_CONTENT_TYPE.0 as u32;
+        const QUANTIZATION_RANGE = ctl_display_setting_flag_t::CTL_DISPLAY_SETTING_FLAG_QUANTIZATION_RANGE.0 as u32;
+        const PICTURE_AR = ctl_display_setting_flag_t::CTL_DISPLAY_SETTING_FLAG_PICTURE_AR.0 as u32;
+        const AUDIO = ctl_display_setting_flag_t::CTL_DISPLAY_SETTING_FLAG_AUDIO.0 as u32;
+    }
+}
+
+bitflags::bitflags! {
+    #[doc(alias = "ctl_display_setting_picture_ar_flags_t")]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct PictureAspectRatios: u32 {
+        const DEFAULT = ctl_display_setting_picture_ar_flag_t::CTL_DISPLAY_SETTING_PICTURE_AR_FLAG_DEFAULT.0 as u32;
+        const DISABLED = ctl_display_setting_picture_ar_flag_t::CTL_DISPLAY_SETTING_PICTURE_AR_FLAG_DISABLED.0 as u32;
+        const AR_4_3 = ctl_display_setting_picture_ar_flag_t::CTL_DISPLAY_SETTING_PICTURE_AR_FLAG_AR_4_3.0 as u32;
+        const AR_16_9 = ctl_display_setting_picture_ar_flag_t::CTL_DISPLAY_SETTING_PICTURE_AR_FLAG_AR_16_9.0 as u32;
+        /// 64:27, also known as 21:9 anamorphic.
+        const AR_64_27 = ctl_display_setting_picture_ar_flag_t::CTL_DISPLAY_SETTING_PICTURE_AR_FLAG_AR_64_27.0 as u32;
+        const AR_256_135 = ctl_display_setting_picture_ar_flag_t::CTL_DISPLAY_SETTING_PICTURE_AR_FLAG_AR_256_135.0 as u32;
+    }
+}
+
+/// State of an on/off display setting. [`SettingState::Default`] hands control back to the
+/// driver when set.
+#[doc(alias = "ctl_display_setting_low_latency_t")]
+#[doc(alias = "ctl_display_setting_sourcetm_t")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SettingState {
+    Default,
+    Disabled,
+    Enabled,
+    /// A state not known to these bindings.
+    Unknown(i32),
+}
+
+impl From<ctl_display_setting_low_latency_t> for SettingState {
+    fn from(state: ctl_display_setting_low_latency_t) -> Self {
+        match state {
+            ctl_display_setting_low_latency_t::CTL_DISPLAY_SETTING_LOW_LATENCY_DEFAULT => {
+                Self::Default
+            }
+            ctl_display_setting_low_latency_t::CTL_DISPLAY_SETTING_LOW_LATENCY_DISABLED => {
+                Self::Disabled
+            }
+            ctl_display_setting_low_latency_t::CTL_DISPLAY_SETTING_LOW_LATENCY_ENABLED => {
+                Self::Enabled
+            }
+            ctl_display_setting_low_latency_t(x) => Self::Unknown(x),
+        }
+    }
+}
+
+impl From<SettingState> for ctl_display_setting_low_latency_t {
+    fn from(state: SettingState) -> Self {
+        match state {
+            SettingState::Default => Self::CTL_DISPLAY_SETTING_LOW_LATENCY_DEFAULT,
+            SettingState::Disabled => Self::CTL_DISPLAY_SETTING_LOW_LATENCY_DISABLED,
+            SettingState::Enabled => Self::CTL_DISPLAY_SETTING_LOW_LATENCY_ENABLED,
+            SettingState::Unknown(x) => Self(x),
+        }
+    }
+}
+
+impl From<ctl_display_setting_sourcetm_t> for SettingState {
+    fn from(state: ctl_display_setting_sourcetm_t) -> Self {
+        match state {
+            ctl_display_setting_sourcetm_t::CTL_DISPLAY_SETTING_SOURCETM_DEFAULT => Self::Default,
+            ctl_display_setting_sourcetm_t::CTL_DISPLAY_SETTING_SOURCETM_DISABLED => Self::Disabled,
+            ctl_display_setting_sourcetm_t::CTL_DISPLAY_SETTING_SOURCETM_ENABLED => Self::Enabled,
+            ctl_display_setting_sourcetm_t(x) => Self::Unknown(x),
+        }
+    }
+}
+
+impl From<SettingState> for ctl_display_setting_sourcetm_t {
+    fn from(state: SettingState) -> Self {
+        match state {
+            SettingState::Default => Self::CTL_DISPLAY_SETTING_SOURCETM_DEFAULT,
+            SettingState::Disabled => Self::CTL_DISPLAY_SETTING_SOURCETM_DISABLED,
+            SettingState::Enabled => Self::CTL_DISPLAY_SETTING_SOURCETM_ENABLED,
+            SettingState::Unknown(x) => Self(x),
+        }
+    }
+}
+
+/// Content type signalled to the display.
+#[doc(alias = "ctl_display_setting_content_type_t")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ContentType {
+    /// Determined by the driver.
+    Default,
+    Disabled,
+    Desktop,
+    Media,
+    Gaming,
+    /// A content type not known to these bindings.
+    Unknown(i32),
+}
+
+impl From<ctl_display_setting_content_type_t> for ContentType {
+    fn from(content_type: ctl_display_setting_content_type_t) -> Self {
+        match content_type {
+            ctl_display_setting_content_type_t::CTL_DISPLAY_SETTING_CONTENT_TYPE_DEFAULT => {
+                Self::Default
+            }
+            ctl_display_setting_content_type_t::CTL_DISPLAY_SETTING_CONTENT_TYPE_DISABLED => {
+                Self::Disabled
+            }
+            ctl_display_setting_content_type_t::CTL_DISPLAY_SETTING_CONTENT_TYPE_DESKTOP => {
+                Self::Desktop
+            }
+            ctl_display_setting_content_type_t::CTL_DISPLAY_SETTING_CONTENT_TYPE_MEDIA => {
+                Self::Media
+            }
+            ctl_display_setting_content_type_t::CTL_DISPLAY_SETTING_CONTENT_TYPE_GAMING => {
+                Self::Gaming
+            }
+            ctl_display_setting_content_type_t(x) => Self::Unknown(x),
+        }
+    }
+}
+
+impl From<ContentType> for ctl_display_setting_content_type_t {
+    fn from(content_type: ContentType) -> Self {
+        match content_type {
+            ContentType::Default => Self::CTL_DISPLAY_SETTING_CONTENT_TYPE_DEFAULT,
+            ContentType::Disabled => Self::CTL_DISPLAY_SETTING_CONTENT_TYPE_DISABLED,
+            ContentType::Desktop => Self::CTL_DISPLAY_SETTING_CONTENT_TYPE_DESKTOP,
+            ContentType::Media => Self::CTL_DISPLAY_SETTING_CONTENT_TYPE_MEDIA,
+            ContentType::Gaming => Self::CTL_DISPLAY_SETTING_CONTENT_TYPE_GAMING,
+            ContentType::Unknown(x) => Self(x),
+        }
+    }
+}
+
+#[doc(alias = "ctl_display_setting_quantization_range_t")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum QuantizationRange {
+    /// Based on the video format.
+    Default,
+    Limited,
+    Full,
+    /// A quantization range not known to these bindings.
+    Unknown(i32),
+}
+
+impl From<ctl_display_setting_quantization_range_t> for QuantizationRange {
+    fn from(range: ctl_display_setting_quantization_range_t) -> Self {
+        match range {
+            ctl_display_setting_quantization_range_t::CTL_DISPLAY_SETTING_QUANTIZATION_RANGE_DEFAULT => Self::Default,
+            ctl_display_setting_quantization_range_t::CTL_DISPLAY_SETTING_QUANTIZATION_RANGE_LIMITED_RANGE => Self::Limited,
+            ctl_display_setting_quantization_range_t::CTL_DISPLAY_SETTING_QUANTIZATION_RANGE_FULL_RANGE => Self::Full,
+            ctl_display_setting_quantization_range_t(x) => Self::Unknown(x),
+        }
+    }
+}
+
+impl From<QuantizationRange> for ctl_display_setting_quantization_range_t {
+    fn from(range: QuantizationRange) -> Self {
+        match range {
+            QuantizationRange::Default => Self::CTL_DISPLAY_SETTING_QUANTIZATION_RANGE_DEFAULT,
+            QuantizationRange::Limited => {
+                Self::CTL_DISPLAY_SETTING_QUANTIZATION_RANGE_LIMITED_RANGE
+            }
+            QuantizationRange::Full => Self::CTL_DISPLAY_SETTING_QUANTIZATION_RANGE_FULL_RANGE,
+            QuantizationRange::Unknown(x) => Self(x),
+        }
+    }
+}
+
+#[doc(alias = "ctl_display_setting_audio_t")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AudioSetting {
+    /// The display audio endpoint is exposed to the OS if the display supports it.
+    Default,
+    /// The display audio endpoint is hidden from the OS.
+    Disabled,
+    /// An audio setting not known to these bindings.
+    Unknown(i32),
+}
+
+impl From<ctl_display_setting_audio_t> for AudioSetting {
+    fn from(audio: ctl_display_setting_audio_t) -> Self {
+        match audio {
+            ctl_display_setting_audio_t::CTL_DISPLAY_SETTING_AUDIO_DEFAULT => Self::Default,
+            ctl_display_setting_audio_t::CTL_DISPLAY_SETTING_AUDIO_DISABLED => Self::Disabled,
+            ctl_display_setting_audio_t(x) => Self::Unknown(x),
+        }
+    }
+}
+
+impl From<AudioSetting> for ctl_display_setting_audio_t {
+    fn from(audio: AudioSetting) -> Self {
+        match audio {
+            AudioSetting::Default => Self::CTL_DISPLAY_SETTING_AUDIO_DEFAULT,
+            AudioSetting::Disabled => Self::CTL_DISPLAY_SETTING_AUDIO_DISABLED,
+            AudioSetting::Unknown(x) => Self(x),
+        }
+    }
+}
+
+/// End display settings signalled through info-frames or secondary data packets.
+#[doc(alias = "ctl_display_settings_t")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DisplaySettings {
+    /// Settings supported by the display.
+    pub supported: DisplaySettingFlags,
+    /// Settings that can be changed through [`DisplayOutput::set_display_settings()`].
+    pub controllable: DisplaySettingFlags,
+    /// Settings for which the values below were reported by the driver.
+    pub valid: DisplaySettingFlags,
+    /// Must be enabled for HDR10+ Gaming.
+    pub low_latency: SettingState,
+    /// Must be enabled for HDR10+ Gaming.
+    pub source_tone_mapping: SettingState,
+    pub content_type: ContentType,
+    pub quantization_range: QuantizationRange,
+    pub supported_picture_aspect_ratios: PictureAspectRatios,
+    /// A single flag out of [`Self::supported_picture_aspect_ratios`].
+    pub picture_aspect_ratio: PictureAspectRatios,
+    pub audio: AudioSetting,
+}
+
+impl From<ctl_display_settings_t> for DisplaySettings {
+    fn from(settings: ctl_display_settings_t) -> Self {
+        Self {
+            supported: DisplaySettingFlags::from_bits_retain(settings.SupportedFlags),
+            controllable: DisplaySettingFlags::from_bits_retain(settings.ControllableFlags),
+            valid: DisplaySettingFlags::from_bits_retain(settings.ValidFlags),
+            low_latency: settings.LowLatency.into(),
+            source_tone_mapping: settings.SourceTM.into(),
+            content_type: settings.ContentType.into(),
+            quantization_range: settings.QuantizationRange.into(),
+            supported_picture_aspect_ratios: PictureAspectRatios::from_bits_retain(
+                settings.SupportedPictureAR,
+            ),
+            picture_aspect_ratio: PictureAspectRatios::from_bits_retain(
+                settings.PictureAR.0 as u32,
+            ),
+            audio: settings.AudioSettings.into(),
+        }
+    }
+}
+
+impl DisplaySettings {
+    /// Settings whose value differs between `self` and `other`.
+    pub fn diff(&self, other: &DisplaySettings) -> DisplaySettingFlags {
+        let mut changed = DisplaySettingFlags::empty();
+        changed.set(
+            DisplaySettingFlags::LOW_LATENCY,
+            self.low_latency != other.low_latency,
+        );
+        changed.set(
+            DisplaySettingFlags::SOURCE_TM,
+            self.source_tone_mapping != other.source_tone_mapping,
+        );
+        changed.set(
+            DisplaySettingFlags::CONTENT_TYPE,
+            self.content_type != other.content_type,
+        );
+        changed.set(
+            DisplaySettingFlags::QUANTIZATION_RANGE,
+            self.quantization_range != other.quantization_range,
+        );
+        changed.set(
+            DisplaySettingFlags::PICTURE_AR,
+            self.picture_aspect_ratio != other.picture_aspect_ratio,
+        );
+        changed.set(DisplaySettingFlags::AUDIO, self.audio != other.audio);
+        changed
+    }
+
+    /// Settings that have to be changed to get from `self`, the current settings, to
+    /// `target`. Fails if any of them is not both supported and controllable.
+    pub fn changes_to(
+        &self,
+        target: &DisplaySettings,
+    ) -> Result<DisplaySettingFlags, DisplaySettingsError> {
+        let changed = self.diff(target);
+
+        let not_controllable = changed.difference(self.supported & self.controllable);
+        if !not_controllable.is_empty() {
+            return Err(DisplaySettingsError::NotControllable(not_controllable));
+        }
+
+        Ok(changed)
+    }
+}
+
+#[derive(Debug)]
+pub enum DisplaySettingsError {
+    /// Some of the changed settings are not both in [`DisplaySettings::supported`] and
+    /// [`DisplaySettings::controllable`].
+    NotControllable(DisplaySettingFlags),
+    Igcl(Error),
+}
+
+impl fmt::Display for DisplaySettingsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotControllable(flags) => {
+                write!(f, "display settings {flags:?} cannot be changed")
+            }
+            Self::Igcl(error) => fmt::Display::fmt(error, f),
+        }
+    }
+}
+
+impl std::error::Error for DisplaySettingsError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Igcl(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<Error> for DisplaySettingsError {
+    fn from(error: Error) -> Self {
+        Self::Igcl(error)
+    }
+}
+
+impl DisplayOutput {
+    fn get_set_display_settings(&self, args: &mut ctl_display_settings_t) -> Result<()> {
+        args.Size = std::mem::size_of::<ctl_display_settings_t>() as u32;
+        args.Version = 0;
+
+        Error::from_result(unsafe {
+            self.control_lib
+                .ctlGetSetDisplaySettings(self.display_output_handle, args)
+        })
+    }
+
+    /// Query the display settings that are both supported and controllable. The values of
+    /// other settings are left at their defaults.
+    #[doc(alias = "ctlGetSetDisplaySettings")]
+    pub fn display_settings(&self) -> Result<DisplaySettings> {
+        // The first pass only returns which settings are supported and controllable
+        let mut args = ctl_display_settings_t::default();
+        self.get_set_display_settings(&mut args)?;
+
+        args = ctl_display_settings_t {
+            ValidFlags: args.SupportedFlags & args.ControllableFlags,
+            ..Default::default()
+        };
+        self.get_set_display_settings(&mut args)?;
+
+        Ok(args.into())
+    }
+
+    /// Apply `settings`, sending only the values that differ from the current settings.
+    /// Only settings that are both supported and controllable can be changed. Returns the
+    /// settings that were changed.
+    pub fn set_display_settings(
+        &self,
+        settings: &DisplaySettings,
+    ) -> Result<DisplaySettingFlags, DisplaySettingsError> {
+        let changed = self.display_settings()?.changes_to(settings)?;
+        if changed.is_empty() {
+            return Ok(changed);
+        }
+
+        let mut args = ctl_display_settings_t {
+            Set: true,
+            ValidFlags: changed.bits(),
+            LowLatency: settings.low_latency.into(),
+            SourceTM: settings.source_tone_mapping.into(),
+            ContentType: settings.content_type.into(),
+            QuantizationRange: settings.quantization_range.into(),
+            PictureAR: ctl_display_setting_picture_ar_flag_t(
+                settings.picture_aspect_ratio.bits() as i32
+            ),
+            AudioSettings: settings.audio.into(),
+            ..Default::default()
+        };
+        self.get_set_display_settings(&mut args)?;
+
+        Ok(changed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings() -> DisplaySettings {
+        DisplaySettings {
+            supported: DisplaySettingFlags::all(),
+            controllable: DisplaySettingFlags::LOW_LATENCY | DisplaySettingFlags::CONTENT_TYPE,
+            ..ctl_display_settings_t::default().into()
+        }
+    }
+
+    #[test]
+    fn diff() {
+        let current = settings();
+        assert_eq!(current.diff(&current), DisplaySettingFlags::empty());
+
+        let target = DisplaySettings {
+            content_type: ContentType::Gaming,
+            ..current
+        };
+        assert_eq!(current.diff(&target), DisplaySettingFlags::CONTENT_TYPE);
+
+        let target = DisplaySettings {
+            low_latency: SettingState::Enabled,
+            audio: AudioSetting::Disabled,
+            ..target
+        };
+        assert_eq!(
+            current.diff(&target),
+            DisplaySettingFlags::LOW_LATENCY
+                | DisplaySettingFlags::CONTENT_TYPE
+                | DisplaySettingFlags::AUDIO
+        );
+
+        // Capabilities are not settings
+        let target = DisplaySettings {
+            controllable: DisplaySettingFlags::empty(),
+            ..current
+        };
+        assert_eq!(current.diff(&target), DisplaySettingFlags::empty());
+    }
+
+    #[test]
+    fn changes_to() {
+        let current = settings();
+        assert_eq!(
+            current.changes_to(&current).unwrap(),
+            DisplaySettingFlags::empty()
+        );
+
+        let target = DisplaySettings {
+            low_latency: SettingState::Enabled,
+            ..current
+        };
+        assert_eq!(
+            current.changes_to(&target).unwrap(),
+            DisplaySettingFlags::LOW_LATENCY
+        );
+    }
+
+    #[test]
+    fn not_controllable() {
+        let current = settings();
+        let target = DisplaySettings {
+            low_latency: SettingState::Enabled,
+            quantization_range: QuantizationRange::Full,
+            ..current
+        };
+        assert!(matches!(
+            current.changes_to(&target),
+            Err(DisplaySettingsError::NotControllable(flags))
+                if flags == DisplaySettingFlags::QUANTIZATION_RANGE
+        ));
+
+        // Controllable but not supported
+        let current = DisplaySettings {
+            supported: DisplaySettingFlags::CONTENT_TYPE,
+            ..current
+        };
+        assert!(matches!(
+            current.changes_to(&DisplaySettings {
+                low_latency: SettingState::Enabled,
+                ..current
+            }),
+            Err(DisplaySettingsError::NotControllable(flags))
+                if flags == DisplaySettingFlags::LOW_LATENCY
+        ));
+    }
+}
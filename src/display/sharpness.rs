@@ -0,0 +1,237 @@
+use std::fmt;
+
+use crate::{
+    display::DisplayOutput,
+    error::{Error, Result},
+    ffi::{
+        ctl_property_range_info_t, ctl_result_t, ctl_sharpness_caps_t,
+        ctl_sharpness_filter_properties_t, ctl_sharpness_filter_type_flag_t,
+        ctl_sharpness_settings_t,
+    },
+};
+
+bitflags::bitflags! {
+    #[doc(alias = "ctl_sharpness_filter_type_flags_t")]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct SharpnessFilterFlags: u32 {
+        const NON_ADAPTIVE = ctl_sharpness_filter_type_flag_t::CTL_SHARPNESS_FILTER_TYPE_FLAG_NON_ADAPTIVE.0 as u32;
+        const ADAPTIVE = ctl_sharpness_filter_type_flag_t::CTL_SHARPNESS_FILTER_TYPE_FLAG_ADAPTIVE.0 as u32;
+    }
+}
+
+#[doc(alias = "ctl_property_range_info_t")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PropertyRange {
+    pub min: f32,
+    pub max: f32,
+    pub step: f32,
+    pub default: f32,
+}
+
+impl PropertyRange {
+    pub fn contains(&self, value: f32) -> bool {
+        (self.min..=self.max).contains(&value)
+    }
+}
+
+impl From<ctl_property_range_info_t> for PropertyRange {
+    fn from(range: ctl_property_range_info_t) -> Self {
+        Self {
+            min: range.min_possible_value,
+            max: range.max_possible_value,
+            step: range.step_size,
+            default: range.default_value,
+        }
+    }
+}
+
+#[doc(alias = "ctl_sharpness_filter_properties_t")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SharpnessFilter {
+    pub filter_type: SharpnessFilterFlags,
+    /// Range of the intensity accepted by this filter.
+    pub intensity: PropertyRange,
+}
+
+impl From<ctl_sharpness_filter_properties_t> for SharpnessFilter {
+    fn from(filter: ctl_sharpness_filter_properties_t) -> Self {
+        Self {
+            filter_type: SharpnessFilterFlags::from_bits_retain(filter.FilterType),
+            intensity: filter.FilterDetails.into(),
+        }
+    }
+}
+
+#[doc(alias = "ctl_sharpness_caps_t")]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SharpnessCaps {
+    pub supported_filters: SharpnessFilterFlags,
+    pub filters: Vec<SharpnessFilter>,
+}
+
+impl SharpnessCaps {
+    pub fn filter(&self, filter_type: SharpnessFilterFlags) -> Option<&SharpnessFilter> {
+        self.filters.iter().find(|f| f.filter_type == filter_type)
+    }
+}
+
+#[doc(alias = "ctl_sharpness_settings_t")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SharpnessSettings {
+    pub enable: bool,
+    /// A single filter type.
+    pub filter_type: SharpnessFilterFlags,
+    pub intensity: f32,
+}
+
+impl From<ctl_sharpness_settings_t> for SharpnessSettings {
+    fn from(settings: ctl_sharpness_settings_t) -> Self {
+        Self {
+            enable: settings.Enable,
+            filter_type: SharpnessFilterFlags::from_bits_retain(settings.FilterType),
+            intensity: settings.Intensity,
+        }
+    }
+}
+
+impl From<SharpnessSettings> for ctl_sharpness_settings_t {
+    fn from(settings: SharpnessSettings) -> Self {
+        Self {
+            Size: std::mem::size_of::<ctl_sharpness_settings_t>() as u32,
+            Version: 0,
+            Enable: settings.enable,
+            FilterType: settings.filter_type.bits(),
+            Intensity: settings.intensity,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum SharpnessError {
+    /// The filter type is not exactly one of the filters supported by the output.
+    #[doc(alias = "CTL_RESULT_ERROR_INVALID_SHARPNESS_FILTER_FLAG")]
+    InvalidFilterFlag,
+    /// The intensity is outside of the range supported by the filter.
+    IntensityOutOfRange {
+        intensity: f32,
+        range: PropertyRange,
+    },
+    Igcl(Error),
+}
+
+impl fmt::Display for SharpnessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidFilterFlag => fmt::Display::fmt(
+                &Error(ctl_result_t::CTL_RESULT_ERROR_INVALID_SHARPNESS_FILTER_FLAG),
+                f,
+            ),
+            Self::IntensityOutOfRange { intensity, range } => write!(
+                f,
+                "sharpness intensity {intensity} is outside of {}..={}",
+                range.min, range.max
+            ),
+            Self::Igcl(error) => fmt::Display::fmt(error, f),
+        }
+    }
+}
+
+impl std::error::Error for SharpnessError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Igcl(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<Error> for SharpnessError {
+    fn from(error: Error) -> Self {
+        match error.0 {
+            ctl_result_t::CTL_RESULT_ERROR_INVALID_SHARPNESS_FILTER_FLAG => Self::InvalidFilterFlag,
+            _ => Self::Igcl(error),
+        }
+    }
+}
+
+impl DisplayOutput {
+    /// Query the sharpness filters supported by this output and their intensity ranges.
+    #[doc(alias = "ctlGetSharpnessCaps")]
+    pub fn sharpness_caps(&self) -> Result<SharpnessCaps> {
+        let mut caps = ctl_sharpness_caps_t {
+            Size: std::mem::size_of::<ctl_sharpness_caps_t>() as u32,
+            Version: 0,
+            ..Default::default()
+        };
+
+        Error::from_result(unsafe {
+            self.control_lib
+                .ctlGetSharpnessCaps(self.display_output_handle, &mut caps)
+        })?;
+
+        let mut filters =
+            vec![ctl_sharpness_filter_properties_t::default(); caps.NumFilterTypes as usize];
+        if !filters.is_empty() {
+            caps.pFilterProperty = filters.as_mut_ptr();
+
+            Error::from_result(unsafe {
+                self.control_lib
+                    .ctlGetSharpnessCaps(self.display_output_handle, &mut caps)
+            })?;
+
+            filters.truncate(caps.NumFilterTypes as usize);
+        }
+
+        Ok(SharpnessCaps {
+            supported_filters: SharpnessFilterFlags::from_bits_retain(caps.SupportedFilterFlags),
+            filters: filters.into_iter().map(SharpnessFilter::from).collect(),
+        })
+    }
+
+    #[doc(alias = "ctlGetCurrentSharpness")]
+    pub fn sharpness(&self) -> Result<SharpnessSettings> {
+        let mut settings = ctl_sharpness_settings_t {
+            Size: std::mem::size_of::<ctl_sharpness_settings_t>() as u32,
+            Version: 0,
+            ..Default::default()
+        };
+
+        Error::from_result(unsafe {
+            self.control_lib
+                .ctlGetCurrentSharpness(self.display_output_handle, &mut settings)
+        })?;
+
+        Ok(settings.into())
+    }
+
+    /// Apply `settings`, after checking the filter type and intensity against
+    /// [`Self::sharpness_caps()`]. The intensity is not checked when disabling sharpness.
+    #[doc(alias = "ctlSetCurrentSharpness")]
+    pub fn set_sharpness(&self, settings: SharpnessSettings) -> Result<(), SharpnessError> {
+        let caps = self.sharpness_caps()?;
+        let filter = caps
+            .filter(settings.filter_type)
+            .filter(|_| caps.supported_filters.contains(settings.filter_type))
+            .ok_or(SharpnessError::InvalidFilterFlag)?;
+
+        if settings.enable && !filter.intensity.contains(settings.intensity) {
+            return Err(SharpnessError::IntensityOutOfRange {
+                intensity: settings.intensity,
+                range: filter.intensity,
+            });
+        }
+
+        let mut args = settings.into();
+        Error::from_result(unsafe {
+            self.control_lib
+                .ctlSetCurrentSharpness(self.display_output_handle, &mut args)
+        })?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,97 @@
+use std::fmt;
+
+use crate::{
+    display::DisplayOutput,
+    error::{Error, Result},
+    ffi::ctl_sw_psr_settings_t,
+};
+
+/// Software panel self refresh state. This is a reserved capability that has to be activated
+/// by Intel for an application before it is reported as supported.
+#[doc(alias = "ctl_sw_psr_settings_t")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SoftwarePsr {
+    pub supported: bool,
+    pub enabled: bool,
+}
+
+#[derive(Debug)]
+pub enum SoftwarePsrError {
+    /// Software PSR is not supported on this output.
+    Unsupported,
+    /// The driver accepted the request but reports a different state afterwards.
+    NotApplied {
+        requested: bool,
+    },
+    Igcl(Error),
+}
+
+impl fmt::Display for SoftwarePsrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unsupported => f.write_str("software PSR is not supported"),
+            Self::NotApplied { requested: true } => f.write_str("software PSR was not enabled"),
+            Self::NotApplied { requested: false } => f.write_str("software PSR was not disabled"),
+            Self::Igcl(error) => fmt::Display::fmt(error, f),
+        }
+    }
+}
+
+impl std::error::Error for SoftwarePsrError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Igcl(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<Error> for SoftwarePsrError {
+    fn from(error: Error) -> Self {
+        Self::Igcl(error)
+    }
+}
+
+impl DisplayOutput {
+    fn get_set_software_psr(&self, set: bool, enable: bool) -> Result<SoftwarePsr> {
+        let mut args = ctl_sw_psr_settings_t {
+            Size: std::mem::size_of::<ctl_sw_psr_settings_t>() as u32,
+            Version: 0,
+            Set: set,
+            Enable: enable,
+            ..Default::default()
+        };
+
+        Error::from_result(unsafe {
+            self.control_lib
+                .ctlSoftwarePSR(self.display_output_handle, &mut args)
+        })?;
+
+        Ok(SoftwarePsr {
+            supported: args.Supported,
+            enabled: args.Enable,
+        })
+    }
+
+    #[doc(alias = "ctlSoftwarePSR")]
+    pub fn software_psr(&self) -> Result<SoftwarePsr> {
+        self.get_set_software_psr(false, false)
+    }
+
+    /// Enable or disable software PSR, after checking that it is supported, and verify that
+    /// the driver reports the requested state afterwards.
+    pub fn set_software_psr(&self, enable: bool) -> Result<(), SoftwarePsrError> {
+        if !self.software_psr()?.supported {
+            return Err(SoftwarePsrError::Unsupported);
+        }
+
+        self.get_set_software_psr(true, enable)?;
+
+        if self.software_psr()?.enabled != enable {
+            return Err(SoftwarePsrError::NotApplied { requested: enable });
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,185 @@
+use crate::{
+    display::{DisplayOutput, DisplayTiming},
+    error::{Error, Result},
+    ffi::ctl_vblank_ts_args_t,
+};
+
+/// Frame pacing statistics computed from successive hardware vblank timestamps of a single
+/// target. All durations are in microseconds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VblankStats {
+    /// Number of refresh intervals the statistics are based on.
+    pub intervals: usize,
+    /// Number of vblanks that elapsed between two samples without being observed.
+    pub missed_vblanks: usize,
+    /// Refresh interval derived from [`DisplayTiming::refresh_rate`].
+    pub nominal_interval: f64,
+    /// Mean measured refresh interval.
+    pub measured_interval: f64,
+    /// Standard deviation of the measured refresh intervals.
+    pub jitter: f64,
+    /// Difference between the measured and the nominal refresh interval; positive when the
+    /// display refreshes slower than advertised.
+    pub drift: f64,
+}
+
+impl VblankStats {
+    /// Compute statistics from timestamps in microseconds, as returned for one target by
+    /// [`DisplayOutput::vblank_timestamps()`], in the order they were sampled.
+    ///
+    /// Repeated timestamps (sampling faster than the display refreshes) are ignored, and
+    /// gaps spanning multiple vblanks are divided by the number of refreshes that elapsed.
+    /// Returns [`None`] when the refresh rate of the timing is not a finite positive number or
+    /// fewer than two distinct timestamps were given.
+    pub fn from_timestamps(timestamps: &[u64], timing: &DisplayTiming) -> Option<Self> {
+        if !timing.refresh_rate.is_finite() || timing.refresh_rate <= 0.0 {
+            return None;
+        }
+        let nominal_interval = 1_000_000.0 / timing.refresh_rate as f64;
+
+        let mut intervals = vec![];
+        let mut missed_vblanks = 0;
+        for pair in timestamps.windows(2) {
+            let Some(delta) = pair[1].checked_sub(pair[0]).filter(|&d| d > 0) else {
+                continue;
+            };
+            let delta = delta as f64;
+            let frames = (delta / nominal_interval).round().max(1.0);
+            missed_vblanks += frames as usize - 1;
+            intervals.push(delta / frames);
+        }
+
+        if intervals.is_empty() {
+            return None;
+        }
+
+        let count = intervals.len() as f64;
+        let measured_interval = intervals.iter().sum::<f64>() / count;
+        let variance = intervals
+            .iter()
+            .map(|i| (i - measured_interval).powi(2))
+            .sum::<f64>()
+            / count;
+
+        Some(Self {
+            intervals: intervals.len(),
+            missed_vblanks,
+            nominal_interval,
+            measured_interval,
+            jitter: variance.sqrt(),
+            drift: measured_interval - nominal_interval,
+        })
+    }
+
+    /// Refresh rate in Hz corresponding to [`Self::measured_interval`].
+    pub fn measured_refresh_rate(&self) -> f64 {
+        1_000_000.0 / self.measured_interval
+    }
+
+    /// Relative drift in parts per million.
+    pub fn drift_ppm(&self) -> f64 {
+        self.drift / self.nominal_interval * 1_000_000.0
+    }
+}
+
+impl DisplayOutput {
+    /// Get the most recent hardware vblank timestamp in microseconds of every child target
+    /// (pipe) driving this display.
+    #[doc(alias = "ctlGetVblankTimestamp")]
+    pub fn vblank_timestamps(&self) -> Result<Vec<u64>> {
+        let mut args = ctl_vblank_ts_args_t {
+            Size: std::mem::size_of::<ctl_vblank_ts_args_t>() as u32,
+            Version: 0,
+            ..Default::default()
+        };
+
+        Error::from_result(unsafe {
+            self.control_lib
+                .ctlGetVblankTimestamp(self.display_output_handle, &mut args)
+        })?;
+
+        let num_targets = (args.NumOfTargets as usize).min(args.VblankTS.len());
+        Ok(args.VblankTS[..num_targets].to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ffi::ctl_display_timing_t;
+
+    fn timing(refresh_rate: f32) -> DisplayTiming {
+        DisplayTiming {
+            refresh_rate,
+            ..ctl_display_timing_t::default().into()
+        }
+    }
+
+    #[test]
+    fn steady() {
+        let stats = VblankStats::from_timestamps(&[0, 20_000, 40_000, 60_000], &timing(50.0));
+        assert_eq!(
+            stats,
+            Some(VblankStats {
+                intervals: 3,
+                missed_vblanks: 0,
+                nominal_interval: 20_000.0,
+                measured_interval: 20_000.0,
+                jitter: 0.0,
+                drift: 0.0,
+            })
+        );
+    }
+
+    #[test]
+    fn jitter() {
+        let stats =
+            VblankStats::from_timestamps(&[0, 19_900, 40_000, 59_900, 80_000], &timing(50.0))
+                .unwrap();
+        assert_eq!(stats.intervals, 4);
+        assert_eq!(stats.measured_interval, 20_000.0);
+        assert_eq!(stats.jitter, 100.0);
+        assert_eq!(stats.drift, 0.0);
+    }
+
+    #[test]
+    fn drift() {
+        let stats = VblankStats::from_timestamps(&[0, 20_020, 40_040], &timing(50.0)).unwrap();
+        assert_eq!(stats.drift, 20.0);
+        assert!((stats.drift_ppm() - 1000.0).abs() < 1e-9);
+        assert!((stats.measured_refresh_rate() - 1_000_000.0 / 20_020.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn missed_and_repeated_vblanks() {
+        let stats =
+            VblankStats::from_timestamps(&[0, 20_000, 20_000, 80_000, 100_000], &timing(50.0))
+                .unwrap();
+        assert_eq!(stats.intervals, 3);
+        assert_eq!(stats.missed_vblanks, 2);
+        assert_eq!(stats.measured_interval, 20_000.0);
+        assert_eq!(stats.jitter, 0.0);
+    }
+
+    #[test]
+    fn too_few_timestamps() {
+        assert_eq!(VblankStats::from_timestamps(&[], &timing(60.0)), None);
+        assert_eq!(VblankStats::from_timestamps(&[1_000], &timing(60.0)), None);
+        assert_eq!(
+            VblankStats::from_timestamps(&[1_000, 1_000, 500], &timing(60.0)),
+            None
+        );
+    }
+
+    #[test]
+    fn invalid_refresh_rate() {
+        let timestamps = [0, 16_667, 33_333];
+        for refresh_rate in [0.0, -60.0, f32::NAN, f32::INFINITY, f32::NEG_INFINITY] {
+            assert_eq!(
+                VblankStats::from_timestamps(&timestamps, &timing(refresh_rate)),
+                None
+            );
+        }
+    }
+}
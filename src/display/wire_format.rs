@@ -0,0 +1,296 @@
+use std::fmt;
+
+use crate::{
+    display::{DisplayOutput, OutputBpcFlags},
+    error::{Error, Result},
+    ffi::{
+        ctl_get_set_wire_format_config_t, ctl_wire_format_color_model_t,
+        ctl_wire_format_operation_type_t, ctl_wire_format_t,
+    },
+};
+
+#[doc(alias = "ctl_wire_format_color_model_t")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ColorModel {
+    Rgb,
+    YCbCr420,
+    YCbCr422,
+    YCbCr444,
+    /// A color model not known to these bindings.
+    Unknown(i32),
+}
+
+impl From<ctl_wire_format_color_model_t> for ColorModel {
+    fn from(model: ctl_wire_format_color_model_t) -> Self {
+        match model {
+            ctl_wire_format_color_model_t::CTL_WIRE_FORMAT_COLOR_MODEL_RGB => Self::Rgb,
+            ctl_wire_format_color_model_t::CTL_WIRE_FORMAT_COLOR_MODEL_YCBCR_420 => Self::YCbCr420,
+            ctl_wire_format_color_model_t::CTL_WIRE_FORMAT_COLOR_MODEL_YCBCR_422 => Self::YCbCr422,
+            ctl_wire_format_color_model_t::CTL_WIRE_FORMAT_COLOR_MODEL_YCBCR_444 => Self::YCbCr444,
+            ctl_wire_format_color_model_t(x) => Self::Unknown(x),
+        }
+    }
+}
+
+impl From<ColorModel> for ctl_wire_format_color_model_t {
+    fn from(model: ColorModel) -> Self {
+        match model {
+            ColorModel::Rgb => Self::CTL_WIRE_FORMAT_COLOR_MODEL_RGB,
+            ColorModel::YCbCr420 => Self::CTL_WIRE_FORMAT_COLOR_MODEL_YCBCR_420,
+            ColorModel::YCbCr422 => Self::CTL_WIRE_FORMAT_COLOR_MODEL_YCBCR_422,
+            ColorModel::YCbCr444 => Self::CTL_WIRE_FORMAT_COLOR_MODEL_YCBCR_444,
+            ColorModel::Unknown(x) => Self(x),
+        }
+    }
+}
+
+/// Color model and color depth of the signal sent to a display.
+#[doc(alias = "ctl_wire_format_t")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WireFormat {
+    pub color_model: ColorModel,
+    /// For supported formats, every color depth available with [`Self::color_model`].
+    pub color_depth: OutputBpcFlags,
+}
+
+impl WireFormat {
+    pub fn new(color_model: ColorModel, color_depth: OutputBpcFlags) -> Self {
+        Self {
+            color_model,
+            color_depth,
+        }
+    }
+
+    /// Whether `other` uses the same color model and only color depths contained in `self`.
+    pub fn supports(&self, other: &WireFormat) -> bool {
+        self.color_model == other.color_model
+            && !other.color_depth.is_empty()
+            && self.color_depth.contains(other.color_depth)
+    }
+
+    /// The wire format the driver is expected to report after `self` is set.
+    ///
+    /// IGCL does not document which depth is applied when several are requested; this assumes
+    /// the lowest one, i.e. the lowest set bit, as the [`OutputBpcFlags`] are ordered by depth.
+    /// [`DisplayOutput::set_wire_format()`] reports [`WireFormatError::NotApplied`] if the
+    /// driver picks a different one.
+    fn applied(&self) -> Self {
+        let lowest = self.color_depth.bits() & self.color_depth.bits().wrapping_neg();
+        Self::new(self.color_model, OutputBpcFlags::from_bits_retain(lowest))
+    }
+}
+
+impl From<ctl_wire_format_t> for WireFormat {
+    fn from(format: ctl_wire_format_t) -> Self {
+        Self {
+            color_model: format.ColorModel.into(),
+            color_depth: OutputBpcFlags::from_bits_retain(format.ColorDepth),
+        }
+    }
+}
+
+impl From<WireFormat> for ctl_wire_format_t {
+    fn from(format: WireFormat) -> Self {
+        Self {
+            Size: std::mem::size_of::<ctl_wire_format_t>() as u32,
+            Version: 0,
+            ColorModel: format.color_model.into(),
+            ColorDepth: format.color_depth.bits(),
+        }
+    }
+}
+
+#[doc(alias = "ctl_get_set_wire_format_config_t")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WireFormatConfig {
+    pub supported: Vec<WireFormat>,
+    pub current: WireFormat,
+}
+
+impl WireFormatConfig {
+    pub fn supports(&self, format: &WireFormat) -> bool {
+        self.supported.iter().any(|s| s.supports(format))
+    }
+}
+
+#[derive(Debug)]
+pub enum WireFormatError {
+    /// The requested wire format is not in [`WireFormatConfig::supported`].
+    Unsupported(WireFormat),
+    /// The driver accepted the request but reports a different wire format afterwards.
+    NotApplied {
+        requested: WireFormat,
+        current: WireFormat,
+    },
+    Igcl(Error),
+}
+
+impl fmt::Display for WireFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unsupported(format) => write!(f, "wire format {format:?} is not supported"),
+            Self::NotApplied { requested, current } => write!(
+                f,
+                "wire format {requested:?} was not applied, current wire format is {current:?}"
+            ),
+            Self::Igcl(error) => fmt::Display::fmt(error, f),
+        }
+    }
+}
+
+impl std::error::Error for WireFormatError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Igcl(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<Error> for WireFormatError {
+    fn from(error: Error) -> Self {
+        Self::Igcl(error)
+    }
+}
+
+impl DisplayOutput {
+    fn get_set_wire_format(
+        &self,
+        operation: ctl_wire_format_operation_type_t,
+        wire_format: Option<WireFormat>,
+    ) -> Result<WireFormatConfig> {
+        let mut args = ctl_get_set_wire_format_config_t {
+            Size: std::mem::size_of::<ctl_get_set_wire_format_config_t>() as u32,
+            Version: 0,
+            Operation: operation,
+            WireFormat: wire_format.map(Into::into).unwrap_or_default(),
+            ..Default::default()
+        };
+
+        Error::from_result(unsafe {
+            self.control_lib
+                .ctlGetSetWireFormat(self.display_output_handle, &mut args)
+        })?;
+
+        Ok(WireFormatConfig {
+            supported: args
+                .SupportedWireFormat
+                .iter()
+                .map(|&format| WireFormat::from(format))
+                .filter(|format| !format.color_depth.is_empty())
+                .collect(),
+            current: args.WireFormat.into(),
+        })
+    }
+
+    /// Query the supported and the current wire format of this output.
+    #[doc(alias = "ctlGetSetWireFormat")]
+    #[doc(alias = "CTL_WIRE_FORMAT_OPERATION_TYPE_GET")]
+    pub fn wire_format(&self) -> Result<WireFormatConfig> {
+        self.get_set_wire_format(
+            ctl_wire_format_operation_type_t::CTL_WIRE_FORMAT_OPERATION_TYPE_GET,
+            None,
+        )
+    }
+
+    /// Apply `wire_format`, after checking that it is supported, and verify that the driver
+    /// reports it as the current wire format afterwards. If multiple color depths are given,
+    /// the lowest one is expected to be applied.
+    #[doc(alias = "CTL_WIRE_FORMAT_OPERATION_TYPE_SET")]
+    pub fn set_wire_format(&self, wire_format: WireFormat) -> Result<(), WireFormatError> {
+        if !self.wire_format()?.supports(&wire_format) {
+            return Err(WireFormatError::Unsupported(wire_format));
+        }
+
+        self.get_set_wire_format(
+            ctl_wire_format_operation_type_t::CTL_WIRE_FORMAT_OPERATION_TYPE_SET,
+            Some(wire_format),
+        )?;
+
+        let requested = wire_format.applied();
+        let current = self.wire_format()?.current;
+        if current != requested {
+            return Err(WireFormatError::NotApplied { requested, current });
+        }
+
+        Ok(())
+    }
+
+    /// Restore the driver's default wire format.
+    #[doc(alias = "CTL_WIRE_FORMAT_OPERATION_TYPE_RESTORE_DEFAULT")]
+    pub fn restore_default_wire_format(&self) -> Result<()> {
+        self.get_set_wire_format(
+            ctl_wire_format_operation_type_t::CTL_WIRE_FORMAT_OPERATION_TYPE_RESTORE_DEFAULT,
+            None,
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rgb(color_depth: OutputBpcFlags) -> WireFormat {
+        WireFormat::new(ColorModel::Rgb, color_depth)
+    }
+
+    #[test]
+    fn supports() {
+        let supported = rgb(OutputBpcFlags::BPC_8 | OutputBpcFlags::BPC_10);
+
+        assert!(supported.supports(&rgb(OutputBpcFlags::BPC_8)));
+        assert!(supported.supports(&supported));
+        assert!(!supported.supports(&rgb(OutputBpcFlags::BPC_12)));
+        assert!(!supported.supports(&rgb(OutputBpcFlags::BPC_8 | OutputBpcFlags::BPC_12)));
+        assert!(!supported.supports(&rgb(OutputBpcFlags::empty())));
+        assert!(!supported.supports(&WireFormat::new(
+            ColorModel::YCbCr444,
+            OutputBpcFlags::BPC_8
+        )));
+    }
+
+    #[test]
+    fn config_supports() {
+        let config = WireFormatConfig {
+            supported: vec![
+                rgb(OutputBpcFlags::BPC_8 | OutputBpcFlags::BPC_10),
+                WireFormat::new(ColorModel::YCbCr420, OutputBpcFlags::BPC_8),
+            ],
+            current: rgb(OutputBpcFlags::BPC_8),
+        };
+
+        assert!(config.supports(&rgb(OutputBpcFlags::BPC_10)));
+        assert!(config.supports(&WireFormat::new(
+            ColorModel::YCbCr420,
+            OutputBpcFlags::BPC_8
+        )));
+        assert!(!config.supports(&WireFormat::new(
+            ColorModel::YCbCr420,
+            OutputBpcFlags::BPC_10
+        )));
+    }
+
+    #[test]
+    fn applied() {
+        assert_eq!(
+            rgb(OutputBpcFlags::BPC_10).applied(),
+            rgb(OutputBpcFlags::BPC_10)
+        );
+        assert_eq!(
+            rgb(OutputBpcFlags::BPC_8 | OutputBpcFlags::BPC_10 | OutputBpcFlags::BPC_12).applied(),
+            rgb(OutputBpcFlags::BPC_8)
+        );
+        assert_eq!(
+            rgb(OutputBpcFlags::BPC_6 | OutputBpcFlags::BPC_12).applied(),
+            rgb(OutputBpcFlags::BPC_6)
+        );
+        assert_eq!(
+            rgb(OutputBpcFlags::empty()).applied(),
+            rgb(OutputBpcFlags::empty())
+        );
+    }
+}
@@ -0,0 +1,181 @@
+use std::{fmt, num::ParseIntError, str::FromStr};
+
+/// Four-part Windows driver version, e.g. `32.0.101.8531`.
+///
+/// Versions are ordered by their parts from left to right, so they can be compared
+/// against a minimum version that is known to contain a fix or feature.
+#[doc(alias = "driver_version")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DriverVersion {
+    pub major: u16,
+    pub minor: u16,
+    pub build: u16,
+    pub revision: u16,
+}
+
+impl DriverVersion {
+    pub const fn new(major: u16, minor: u16, build: u16, revision: u16) -> Self {
+        Self {
+            major,
+            minor,
+            build,
+            revision,
+        }
+    }
+
+    /// Decode the packed 64-bit version, which stores each part in 16 bits from most to
+    /// least significant.
+    pub const fn from_bits(bits: u64) -> Self {
+        Self::new(
+            (bits >> 48) as u16,
+            (bits >> 32) as u16,
+            (bits >> 16) as u16,
+            bits as u16,
+        )
+    }
+
+    pub const fn to_bits(self) -> u64 {
+        (self.major as u64) << 48
+            | (self.minor as u64) << 32
+            | (self.build as u64) << 16
+            | self.revision as u64
+    }
+}
+
+impl From<u64> for DriverVersion {
+    fn from(bits: u64) -> Self {
+        Self::from_bits(bits)
+    }
+}
+
+impl From<DriverVersion> for u64 {
+    fn from(version: DriverVersion) -> Self {
+        version.to_bits()
+    }
+}
+
+impl fmt::Display for DriverVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}.{}.{}.{}",
+            self.major, self.minor, self.build, self.revision
+        )
+    }
+}
+
+/// Error returned when parsing a [`DriverVersion`] from a string fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseDriverVersionError {
+    /// The version does not consist of exactly four `.`-separated parts.
+    InvalidPartCount(usize),
+    /// One of the parts is not a valid 16-bit number.
+    InvalidPart(ParseIntError),
+}
+
+impl fmt::Display for ParseDriverVersionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidPartCount(count) => {
+                write!(f, "expected 4 version parts, found {count}")
+            }
+            Self::InvalidPart(error) => write!(f, "invalid version part: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseDriverVersionError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidPartCount(_) => None,
+            Self::InvalidPart(error) => Some(error),
+        }
+    }
+}
+
+impl FromStr for DriverVersion {
+    type Err = ParseDriverVersionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts = s
+            .trim()
+            .split('.')
+            .map(str::parse)
+            .collect::<Result<Vec<u16>, _>>()
+            .map_err(ParseDriverVersionError::InvalidPart)?;
+
+        match parts[..] {
+            [major, minor, build, revision] => Ok(Self::new(major, minor, build, revision)),
+            _ => Err(ParseDriverVersionError::InvalidPartCount(parts.len())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            "32.0.101.8531".parse(),
+            Ok(DriverVersion::new(32, 0, 101, 8531))
+        );
+        assert_eq!(
+            " 31.0.101.4502\n".parse(),
+            Ok(DriverVersion::new(31, 0, 101, 4502))
+        );
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert_eq!(
+            "32.0.101".parse::<DriverVersion>(),
+            Err(ParseDriverVersionError::InvalidPartCount(3))
+        );
+        assert_eq!(
+            "32.0.101.8531.1".parse::<DriverVersion>(),
+            Err(ParseDriverVersionError::InvalidPartCount(5))
+        );
+        assert!(matches!(
+            "32.0.101.65536".parse::<DriverVersion>(),
+            Err(ParseDriverVersionError::InvalidPart(_))
+        ));
+        assert!(matches!(
+            "32.0.x.8531".parse::<DriverVersion>(),
+            Err(ParseDriverVersionError::InvalidPart(_))
+        ));
+        assert!(matches!(
+            "".parse::<DriverVersion>(),
+            Err(ParseDriverVersionError::InvalidPart(_))
+        ));
+    }
+
+    #[test]
+    fn display_round_trip() {
+        let version = DriverVersion::new(32, 0, 101, 8531);
+        assert_eq!(version.to_string(), "32.0.101.8531");
+        assert_eq!(version.to_string().parse(), Ok(version));
+    }
+
+    #[test]
+    fn bits_round_trip() {
+        let bits = 0x0020_0000_0065_2153;
+        let version = DriverVersion::from_bits(bits);
+        assert_eq!(version, DriverVersion::new(32, 0, 101, 8531));
+        assert_eq!(version.to_bits(), bits);
+        assert_eq!(u64::from(DriverVersion::from(u64::MAX)), u64::MAX);
+        assert_eq!(DriverVersion::from_bits(0), DriverVersion::default());
+    }
+
+    #[test]
+    fn ordering() {
+        let version = DriverVersion::new(32, 0, 101, 8531);
+        assert!(DriverVersion::new(32, 0, 101, 8530) < version);
+        assert!(DriverVersion::new(31, 9, 999, 9999) < version);
+        assert!(DriverVersion::new(32, 0, 102, 0) > version);
+        assert!(DriverVersion::new(33, 0, 0, 0) > version);
+        assert_eq!(version.cmp(&version), std::cmp::Ordering::Equal);
+    }
+}
@@ -0,0 +1,404 @@
+use std::{fmt, sync::Arc};
+
+use crate::{
+    device_adapter::DeviceAdapter,
+    display::{DisplayOutput, DisplayTiming},
+    error::{Error, Result},
+    ffi::{
+        ctl_device_adapter_handle_t, ctl_display_output_handle_t, ctl_display_timing_t,
+        ctl_genlock_args_t, ctl_genlock_display_info_t, ctl_genlock_operation_t,
+        ctl_genlock_target_mode_list_t, ctl_result_t, ControlLib,
+    },
+};
+
+/// Error returned by genlock operations, which span multiple adapters.
+#[derive(Debug)]
+pub struct GenlockError {
+    pub error: Error,
+    /// Index of the adapter that caused the failure, in the order of [`Genlock::adapters()`]
+    /// or the slice passed to [`Genlock::topology()`], if reported by the driver.
+    pub failed_adapter: Option<usize>,
+}
+
+impl fmt::Display for GenlockError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.failed_adapter {
+            Some(index) => write!(f, "{} (on adapter {index})", self.error),
+            None => fmt::Display::fmt(&self.error, f),
+        }
+    }
+}
+
+impl std::error::Error for GenlockError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// An adapter taking part in a genlock topology, together with its synchronized displays.
+pub struct GenlockSystem<'a> {
+    pub adapter: &'a DeviceAdapter,
+    pub displays: Vec<&'a DisplayOutput>,
+}
+
+/// Genlock state and supported target modes of a single [`GenlockSystem`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GenlockTimingDetails {
+    pub is_enabled: bool,
+    /// Whether genlock can be enabled or disabled with the given topology.
+    pub is_possible: bool,
+    /// Target modes supported by every display, in the order of [`GenlockSystem::displays`].
+    pub target_modes: Vec<Vec<DisplayTiming>>,
+}
+
+#[doc(alias = "ctl_genlock_display_info_t")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GenlockDisplayInfo {
+    /// Compare against [`DisplayOutput::display_output_handle()`] to find the output.
+    pub display_output_handle: ctl_display_output_handle_t,
+    pub is_primary: bool,
+}
+
+/// Genlock topology currently applied on an adapter.
+#[doc(alias = "ctl_genlock_topology_t")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenlockTopology {
+    pub is_enabled: bool,
+    pub is_primary_system: bool,
+    pub common_target_mode: DisplayTiming,
+    pub displays: Vec<GenlockDisplayInfo>,
+}
+
+/// Display genlock configuration, synchronizing the displays of a primary system and any
+/// number of secondary systems to the first display of the primary system.
+pub struct Genlock<'a> {
+    pub primary: GenlockSystem<'a>,
+    pub secondaries: Vec<GenlockSystem<'a>>,
+    /// Target mode applied on all displays when enabling genlock. Defaults to the timing
+    /// currently applied on the primary display.
+    pub common_target_mode: Option<DisplayTiming>,
+}
+
+/// Raw per-adapter arguments, keeping the buffers that the pointers in `args` refer to alive.
+struct RawSystem {
+    args: ctl_genlock_args_t,
+    display_info: Vec<ctl_genlock_display_info_t>,
+    mode_lists: Vec<ctl_genlock_target_mode_list_t>,
+    target_modes: Vec<Vec<ctl_display_timing_t>>,
+}
+
+impl RawSystem {
+    fn new(operation: ctl_genlock_operation_t) -> Self {
+        Self {
+            args: ctl_genlock_args_t {
+                Size: std::mem::size_of::<ctl_genlock_args_t>() as u32,
+                Version: 0,
+                Operation: operation,
+                ..Default::default()
+            },
+            display_info: vec![],
+            mode_lists: vec![],
+            target_modes: vec![],
+        }
+    }
+
+    /// Point the topology at the owned buffers, which must not be resized afterwards. Fails
+    /// with [`CTL_RESULT_ERROR_INVALID_SIZE`] when there are more displays than the topology
+    /// can count.
+    ///
+    /// [`CTL_RESULT_ERROR_INVALID_SIZE`]: ctl_result_t::CTL_RESULT_ERROR_INVALID_SIZE
+    fn bind(&mut self) -> Result<()> {
+        for (list, modes) in self.mode_lists.iter_mut().zip(&mut self.target_modes) {
+            list.pTargetModes = if modes.is_empty() {
+                std::ptr::null_mut()
+            } else {
+                modes.as_mut_ptr()
+            };
+        }
+
+        let topology = &mut self.args.GenlockTopology;
+        topology.NumGenlockDisplays = u8::try_from(self.display_info.len())
+            .map_err(|_| Error(ctl_result_t::CTL_RESULT_ERROR_INVALID_SIZE))?;
+        topology.pGenlockDisplayInfo = if self.display_info.is_empty() {
+            std::ptr::null_mut()
+        } else {
+            self.display_info.as_mut_ptr()
+        };
+        topology.pGenlockModeList = if self.mode_lists.is_empty() {
+            std::ptr::null_mut()
+        } else {
+            self.mode_lists.as_mut_ptr()
+        };
+
+        Ok(())
+    }
+}
+
+fn get_set_genlock(
+    control_lib: &ControlLib,
+    mut adapters: Vec<ctl_device_adapter_handle_t>,
+    systems: &mut [RawSystem],
+) -> Result<(), GenlockError> {
+    let mut args: Vec<ctl_genlock_args_t> = systems
+        .iter_mut()
+        .enumerate()
+        .map(|(index, system)| {
+            system.bind().map_err(|error| GenlockError {
+                error,
+                failed_adapter: Some(index),
+            })?;
+            Ok(system.args)
+        })
+        .collect::<Result<_, GenlockError>>()?;
+    let mut failed_adapter: ctl_device_adapter_handle_t = std::ptr::null_mut();
+
+    let result = Error::from_result(unsafe {
+        control_lib.ctlGetSetDisplayGenlock(
+            adapters.as_mut_ptr(),
+            args.as_mut_ptr(),
+            adapters.len() as u32,
+            &mut failed_adapter,
+        )
+    });
+
+    for (system, args) in systems.iter_mut().zip(args) {
+        system.args = args;
+    }
+
+    result.map_err(|error| GenlockError {
+        error,
+        failed_adapter: adapters
+            .iter()
+            .position(|&handle| !failed_adapter.is_null() && handle == failed_adapter),
+    })
+}
+
+impl<'a> Genlock<'a> {
+    pub fn new(primary: GenlockSystem<'a>, secondaries: Vec<GenlockSystem<'a>>) -> Self {
+        Self {
+            primary,
+            secondaries,
+            common_target_mode: None,
+        }
+    }
+
+    /// All systems in this topology, starting with the primary.
+    pub fn adapters(&self) -> impl Iterator<Item = &GenlockSystem<'a>> {
+        std::iter::once(&self.primary).chain(&self.secondaries)
+    }
+
+    fn control_lib(&self) -> &Arc<ControlLib> {
+        &self.primary.adapter.control_lib
+    }
+
+    fn raw_systems(&self, operation: ctl_genlock_operation_t) -> Vec<RawSystem> {
+        let common_target_mode = self
+            .common_target_mode
+            .or_else(|| self.primary.displays.first().map(|d| *d.timing()))
+            .map(ctl_display_timing_t::from)
+            .unwrap_or_default();
+
+        self.adapters()
+            .enumerate()
+            .map(|(index, system)| {
+                let is_primary_system = index == 0;
+                let mut raw = RawSystem::new(operation);
+                raw.args.GenlockTopology.IsPrimaryGenlockSystem = is_primary_system;
+                raw.args.GenlockTopology.CommonTargetMode = common_target_mode;
+                raw.display_info = system
+                    .displays
+                    .iter()
+                    .enumerate()
+                    .map(|(i, display)| ctl_genlock_display_info_t {
+                        hDisplayOutput: display.display_output_handle,
+                        IsPrimary: is_primary_system && i == 0,
+                    })
+                    .collect();
+                raw.mode_lists = system
+                    .displays
+                    .iter()
+                    .map(|display| ctl_genlock_target_mode_list_t {
+                        hDisplayOutput: display.display_output_handle,
+                        NumModes: 0,
+                        pTargetModes: std::ptr::null_mut(),
+                    })
+                    .collect();
+                raw.target_modes = vec![vec![]; system.displays.len()];
+                raw
+            })
+            .collect()
+    }
+
+    fn run(&self, operation: ctl_genlock_operation_t) -> Result<Vec<RawSystem>, GenlockError> {
+        let mut systems = self.raw_systems(operation);
+        get_set_genlock(
+            self.control_lib(),
+            self.adapters()
+                .map(|s| s.adapter.device_adapter_handle)
+                .collect(),
+            &mut systems,
+        )?;
+        Ok(systems)
+    }
+
+    /// Query whether genlock is enabled and possible on every system, together with the
+    /// target modes supported by each display.
+    #[doc(alias = "ctlGetSetDisplayGenlock")]
+    #[doc(alias = "CTL_GENLOCK_OPERATION_GET_TIMING_DETAILS")]
+    pub fn timing_details(&self) -> Result<Vec<GenlockTimingDetails>, GenlockError> {
+        let operation = ctl_genlock_operation_t::CTL_GENLOCK_OPERATION_GET_TIMING_DETAILS;
+
+        // The first pass only returns the number of target modes per display
+        let mut systems = self.run(operation)?;
+        for system in &mut systems {
+            system.target_modes = system
+                .mode_lists
+                .iter()
+                .map(|list| {
+                    vec![
+                        ctl_display_timing_t {
+                            Size: std::mem::size_of::<ctl_display_timing_t>() as u32,
+                            ..Default::default()
+                        };
+                        list.NumModes as usize
+                    ]
+                })
+                .collect();
+        }
+
+        get_set_genlock(
+            self.control_lib(),
+            self.adapters()
+                .map(|s| s.adapter.device_adapter_handle)
+                .collect(),
+            &mut systems,
+        )?;
+
+        Ok(systems
+            .into_iter()
+            .map(|system| GenlockTimingDetails {
+                is_enabled: system.args.IsGenlockEnabled,
+                is_possible: system.args.IsGenlockPossible,
+                target_modes: system
+                    .mode_lists
+                    .iter()
+                    .zip(system.target_modes)
+                    .map(|(list, mut modes)| {
+                        modes.truncate(list.NumModes as usize);
+                        modes.into_iter().map(DisplayTiming::from).collect()
+                    })
+                    .collect(),
+            })
+            .collect())
+    }
+
+    /// Let the driver verify that this topology is genlock capable.
+    #[doc(alias = "CTL_GENLOCK_OPERATION_VALIDATE")]
+    pub fn validate(&self) -> Result<bool, GenlockError> {
+        let systems = self.run(ctl_genlock_operation_t::CTL_GENLOCK_OPERATION_VALIDATE)?;
+        Ok(systems.iter().all(|s| s.args.IsGenlockPossible))
+    }
+
+    #[doc(alias = "CTL_GENLOCK_OPERATION_ENABLE")]
+    pub fn enable(&self) -> Result<(), GenlockError> {
+        self.run(ctl_genlock_operation_t::CTL_GENLOCK_OPERATION_ENABLE)?;
+        Ok(())
+    }
+
+    #[doc(alias = "CTL_GENLOCK_OPERATION_DISABLE")]
+    pub fn disable(&self) -> Result<(), GenlockError> {
+        self.run(ctl_genlock_operation_t::CTL_GENLOCK_OPERATION_DISABLE)?;
+        Ok(())
+    }
+
+    /// Query the genlock topology currently applied on `adapters`, in the same order.
+    #[doc(alias = "CTL_GENLOCK_OPERATION_GET_TOPOLOGY")]
+    pub fn topology(adapters: &[&DeviceAdapter]) -> Result<Vec<GenlockTopology>, GenlockError> {
+        let Some(first) = adapters.first() else {
+            return Ok(vec![]);
+        };
+        let handles = || adapters.iter().map(|a| a.device_adapter_handle).collect();
+        let operation = ctl_genlock_operation_t::CTL_GENLOCK_OPERATION_GET_TOPOLOGY;
+
+        // The first pass only returns the number of displays per adapter
+        let mut systems: Vec<RawSystem> =
+            adapters.iter().map(|_| RawSystem::new(operation)).collect();
+        get_set_genlock(&first.control_lib, handles(), &mut systems)?;
+
+        for system in &mut systems {
+            let num_displays = system.args.GenlockTopology.NumGenlockDisplays as usize;
+            system.display_info = vec![
+                ctl_genlock_display_info_t {
+                    hDisplayOutput: std::ptr::null_mut(),
+                    IsPrimary: false,
+                };
+                num_displays
+            ];
+        }
+        get_set_genlock(&first.control_lib, handles(), &mut systems)?;
+
+        Ok(systems
+            .into_iter()
+            .map(|system| {
+                let topology = system.args.GenlockTopology;
+                let mut display_info = system.display_info;
+                display_info.truncate(topology.NumGenlockDisplays as usize);
+
+                GenlockTopology {
+                    is_enabled: system.args.IsGenlockEnabled,
+                    is_primary_system: topology.IsPrimaryGenlockSystem,
+                    common_target_mode: topology.CommonTargetMode.into(),
+                    displays: display_info
+                        .into_iter()
+                        .map(|info| GenlockDisplayInfo {
+                            display_output_handle: info.hDisplayOutput,
+                            is_primary: info.IsPrimary,
+                        })
+                        .collect(),
+                }
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn system(num_displays: usize) -> RawSystem {
+        let mut system = RawSystem::new(ctl_genlock_operation_t::CTL_GENLOCK_OPERATION_ENABLE);
+        system.display_info = vec![
+            ctl_genlock_display_info_t {
+                hDisplayOutput: std::ptr::null_mut(),
+                IsPrimary: false,
+            };
+            num_displays
+        ];
+        system
+    }
+
+    #[test]
+    fn bind() {
+        let mut empty = system(0);
+        assert!(empty.bind().is_ok());
+        assert_eq!(empty.args.GenlockTopology.NumGenlockDisplays, 0);
+        assert!(empty.args.GenlockTopology.pGenlockDisplayInfo.is_null());
+
+        let mut full = system(255);
+        assert!(full.bind().is_ok());
+        assert_eq!(full.args.GenlockTopology.NumGenlockDisplays, 255);
+        assert_eq!(
+            full.args.GenlockTopology.pGenlockDisplayInfo,
+            full.display_info.as_mut_ptr()
+        );
+    }
+
+    #[test]
+    fn bind_too_many_displays() {
+        assert!(matches!(
+            system(256).bind(),
+            Err(Error(ctl_result_t::CTL_RESULT_ERROR_INVALID_SIZE))
+        ));
+    }
+}
@@ -0,0 +1,167 @@
+use std::fmt;
+
+use crate::{
+    adapter_info::AdapterPropertiesFlags,
+    device_adapter::DeviceAdapter,
+    error::Error,
+    ffi::{ctl_device_adapter_handle_t, ctl_lda_args_t, ctl_result_t},
+};
+
+/// Maximum number of adapters in a Linked Display Adapter (LDA) chain, including the primary.
+pub const MAX_LINKED_ADAPTERS: usize = 4;
+
+/// Role of an adapter in a Linked Display Adapter (LDA) chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LdaRole {
+    /// The adapter is not linked.
+    None,
+    /// The lead adapter, which owns the displays of the chain.
+    Primary,
+    Secondary,
+}
+
+impl From<AdapterPropertiesFlags> for LdaRole {
+    fn from(flags: AdapterPropertiesFlags) -> Self {
+        if flags.contains(AdapterPropertiesFlags::LDA_PRIMARY) {
+            Self::Primary
+        } else if flags.contains(AdapterPropertiesFlags::LDA_SECONDARY) {
+            Self::Secondary
+        } else {
+            Self::None
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum LdaError {
+    #[doc(alias = "CTL_RESULT_ERROR_ADAPTER_ALREADY_LINKED")]
+    AlreadyLinked,
+    /// Only identical adapters can be linked.
+    #[doc(alias = "CTL_RESULT_ERROR_ADAPTER_NOT_IDENTICAL")]
+    NotIdentical,
+    /// The operation must be performed on the LDA primary adapter.
+    #[doc(alias = "CTL_RESULT_ERROR_ADAPTER_NOT_SUPPORTED_ON_LDA_SECONDARY")]
+    NotSupportedOnLdaSecondary,
+    /// More than [`MAX_LINKED_ADAPTERS`] adapters were given.
+    TooManyAdapters(usize),
+    Igcl(Error),
+}
+
+impl fmt::Display for LdaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AlreadyLinked => fmt::Display::fmt(
+                &Error(ctl_result_t::CTL_RESULT_ERROR_ADAPTER_ALREADY_LINKED),
+                f,
+            ),
+            Self::NotIdentical => fmt::Display::fmt(
+                &Error(ctl_result_t::CTL_RESULT_ERROR_ADAPTER_NOT_IDENTICAL),
+                f,
+            ),
+            Self::NotSupportedOnLdaSecondary => fmt::Display::fmt(
+                &Error(ctl_result_t::CTL_RESULT_ERROR_ADAPTER_NOT_SUPPORTED_ON_LDA_SECONDARY),
+                f,
+            ),
+            Self::TooManyAdapters(count) => write!(
+                f,
+                "{count} adapters given, at most {MAX_LINKED_ADAPTERS} can be linked"
+            ),
+            Self::Igcl(error) => fmt::Display::fmt(error, f),
+        }
+    }
+}
+
+impl std::error::Error for LdaError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Igcl(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<Error> for LdaError {
+    fn from(error: Error) -> Self {
+        match error.0 {
+            ctl_result_t::CTL_RESULT_ERROR_ADAPTER_ALREADY_LINKED => Self::AlreadyLinked,
+            ctl_result_t::CTL_RESULT_ERROR_ADAPTER_NOT_IDENTICAL => Self::NotIdentical,
+            ctl_result_t::CTL_RESULT_ERROR_ADAPTER_NOT_SUPPORTED_ON_LDA_SECONDARY => {
+                Self::NotSupportedOnLdaSecondary
+            }
+            _ => Self::Igcl(error),
+        }
+    }
+}
+
+impl DeviceAdapter {
+    /// Role of this adapter in a Linked Display Adapter chain, as reported when the adapter
+    /// was enumerated.
+    pub fn lda_role(&self) -> LdaRole {
+        AdapterPropertiesFlags::from_bits_retain(
+            self.adapter_properties.graphics_adapter_properties,
+        )
+        .into()
+    }
+
+    /// Link `secondaries` to this adapter, which becomes the LDA primary.
+    #[doc(alias = "ctlLinkDisplayAdapters")]
+    pub fn link_display_adapters(&self, secondaries: &[&DeviceAdapter]) -> Result<(), LdaError> {
+        let mut adapters: Vec<ctl_device_adapter_handle_t> = std::iter::once(self)
+            .chain(secondaries.iter().copied())
+            .map(|adapter| adapter.device_adapter_handle)
+            .collect();
+        if adapters.len() > MAX_LINKED_ADAPTERS {
+            return Err(LdaError::TooManyAdapters(adapters.len()));
+        }
+
+        let mut args = ctl_lda_args_t {
+            Size: std::mem::size_of::<ctl_lda_args_t>() as u32,
+            Version: 0,
+            NumAdapters: adapters.len() as u8,
+            hLinkedAdapters: adapters.as_mut_ptr(),
+            Reserved: [0; 4],
+        };
+
+        Error::from_result(unsafe {
+            self.control_lib
+                .ctlLinkDisplayAdapters(self.device_adapter_handle, &mut args)
+        })?;
+
+        Ok(())
+    }
+
+    /// Unlink all adapters linked to this LDA primary adapter.
+    #[doc(alias = "ctlUnlinkDisplayAdapters")]
+    pub fn unlink_display_adapters(&self) -> Result<(), LdaError> {
+        Error::from_result(unsafe {
+            self.control_lib
+                .ctlUnlinkDisplayAdapters(self.device_adapter_handle)
+        })?;
+
+        Ok(())
+    }
+
+    /// Handles of the adapters linked to this LDA primary adapter, starting with the primary
+    /// itself. Compare against [`DeviceAdapter::device_adapter_handle()`] to find the adapters.
+    #[doc(alias = "ctlGetLinkedDisplayAdapters")]
+    pub fn linked_display_adapters(&self) -> Result<Vec<ctl_device_adapter_handle_t>, LdaError> {
+        let mut adapters = vec![std::ptr::null_mut(); MAX_LINKED_ADAPTERS];
+
+        let mut args = ctl_lda_args_t {
+            Size: std::mem::size_of::<ctl_lda_args_t>() as u32,
+            Version: 0,
+            NumAdapters: adapters.len() as u8,
+            hLinkedAdapters: adapters.as_mut_ptr(),
+            Reserved: [0; 4],
+        };
+
+        Error::from_result(unsafe {
+            self.control_lib
+                .ctlGetLinkedDisplayAdapters(self.device_adapter_handle, &mut args)
+        })?;
+
+        adapters.truncate((args.NumAdapters as usize).min(MAX_LINKED_ADAPTERS));
+        Ok(adapters)
+    }
+}
@@ -17,9 +17,17 @@ use crate::{
 #[allow(nonstandard_style)]
 pub mod ffi;
 
+pub mod adapter_info;
+pub mod colour;
+pub mod combined_display;
 pub mod device_adapter;
+pub mod display;
+pub mod driver_version;
 pub mod error;
+pub mod genlock;
+pub mod lda;
 pub mod memory;
+pub mod telemetry;
 
 pub struct Igcl {
     api_handle: ctl_api_handle_t,
@@ -8,6 +8,8 @@ use crate::{
 };
 
 #[doc(alias = "ctl_mem_state_t")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MemoryState {
     pub total: u64,
     pub free: u64,
@@ -0,0 +1,357 @@
+use crate::{
+    device_adapter::{DeviceAdapter, Telemetry, TelemetryItem, UnitKind, Value},
+    error::Result,
+};
+
+/// Rates derived from the monotonic counters of two successive [`Telemetry`] samples.
+///
+/// Every rate is [`None`] when the underlying counter is unsupported in either sample,
+/// changed units or data type in between, or ran backwards in a way that cannot be
+/// explained by the counter wrapping around.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TelemetryRates {
+    /// Time between the two samples, in seconds.
+    pub interval: f64,
+    /// Average GPU chip power, in Watts.
+    pub gpu_power: Option<f64>,
+    /// Average power of the local memory modules, in Watts.
+    pub vram_power: Option<f64>,
+    /// Average total card power, in Watts.
+    pub card_power: Option<f64>,
+    /// Percentage of time that any GPU engine was busy.
+    pub global_activity: Option<f64>,
+    /// Percentage of time that any 3D render/compute engine was busy.
+    pub render_compute_activity: Option<f64>,
+    /// Percentage of time that any media engine was busy.
+    pub media_activity: Option<f64>,
+    /// Average read bandwidth from the memory modules, in MB/s.
+    pub vram_read_bandwidth: Option<f64>,
+    /// Average write bandwidth to the memory modules, in MB/s.
+    pub vram_write_bandwidth: Option<f64>,
+}
+
+impl TelemetryRates {
+    /// Compute the rates between two samples, where `previous` was taken before `current`.
+    ///
+    /// Returns [`None`] if the timestamp is unsupported or did not advance between the samples.
+    pub fn between(previous: &Telemetry, current: &Telemetry) -> Option<Self> {
+        let interval = counter_delta(
+            &previous.time_stamp,
+            &current.time_stamp,
+            UnitKind::TimeSeconds,
+        )?;
+        if !interval.is_finite() || interval <= 0.0 {
+            return None;
+        }
+
+        let power = |prev: &TelemetryItem, cur: &TelemetryItem| {
+            counter_delta(prev, cur, UnitKind::EnergyJoules).map(|joules| joules / interval)
+        };
+        let activity = |prev: &TelemetryItem, cur: &TelemetryItem| {
+            counter_delta(prev, cur, UnitKind::TimeSeconds).map(|busy| busy / interval * 100.0)
+        };
+        let bandwidth = |prev: &TelemetryItem, cur: &TelemetryItem| {
+            counter_delta(prev, cur, UnitKind::MemoryBytes)
+                .map(|bytes| bytes / interval / 1_000_000.0)
+        };
+
+        Some(Self {
+            interval,
+            gpu_power: power(&previous.gpu_energy_counter, &current.gpu_energy_counter),
+            vram_power: power(&previous.vram_energy_counter, &current.vram_energy_counter),
+            card_power: power(
+                &previous.total_card_energy_counter,
+                &current.total_card_energy_counter,
+            ),
+            global_activity: activity(
+                &previous.global_activity_counter,
+                &current.global_activity_counter,
+            ),
+            render_compute_activity: activity(
+                &previous.render_compute_activity_counter,
+                &current.render_compute_activity_counter,
+            ),
+            media_activity: activity(
+                &previous.media_activity_counter,
+                &current.media_activity_counter,
+            ),
+            vram_read_bandwidth: bandwidth(
+                &previous.vram_read_bandwidth_counter,
+                &current.vram_read_bandwidth_counter,
+            ),
+            vram_write_bandwidth: bandwidth(
+                &previous.vram_write_bandwidth_counter,
+                &current.vram_write_bandwidth_counter,
+            ),
+        })
+    }
+}
+
+/// Keeps the most recent [`Telemetry`] sample around to turn the monotonic counters
+/// of the next sample into [`TelemetryRates`].
+#[derive(Debug, Default)]
+pub struct TelemetrySampler {
+    previous: Option<Telemetry>,
+}
+
+impl TelemetrySampler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Query a new sample from `adapter` and compute the rates since the previous one.
+    ///
+    /// Returns `Ok(None)` on the first call, as no rates can be derived from a single sample.
+    #[doc(alias = "ctlPowerTelemetryGet")]
+    pub fn sample(&mut self, adapter: &DeviceAdapter) -> Result<Option<TelemetryRates>> {
+        Ok(self.push(adapter.power_telemetry()?))
+    }
+
+    /// Record `telemetry` as the latest sample and compute the rates since the previous one.
+    ///
+    /// The sample is kept even if no rates could be derived, so that a single bad sample
+    /// does not poison the next interval.
+    pub fn push(&mut self, telemetry: Telemetry) -> Option<TelemetryRates> {
+        let rates = self
+            .previous
+            .as_ref()
+            .and_then(|previous| TelemetryRates::between(previous, &telemetry));
+        self.previous = Some(telemetry);
+        rates
+    }
+
+    /// The most recently recorded sample, if any.
+    pub fn previous(&self) -> Option<&Telemetry> {
+        self.previous.as_ref()
+    }
+
+    /// Forget the previous sample, e.g. after the device was reset.
+    pub fn reset(&mut self) {
+        self.previous = None;
+    }
+}
+
+/// Difference between two snapshots of a monotonic counter.
+///
+/// Integer counters are assumed to wrap around at the width of their data type, whereas
+/// floating-point counters that run backwards are treated as having been reset.
+fn counter_delta(previous: &TelemetryItem, current: &TelemetryItem, unit: UnitKind) -> Option<f64> {
+    let (TelemetryItem(Some(previous)), TelemetryItem(Some(current))) = (previous, current) else {
+        return None;
+    };
+
+    if previous.kind() != unit || current.kind() != unit {
+        return None;
+    }
+
+    Some(match (previous.value(), current.value()) {
+        (Value::U8(p), Value::U8(c)) => c.wrapping_sub(*p) as f64,
+        (Value::U16(p), Value::U16(c)) => c.wrapping_sub(*p) as f64,
+        (Value::U32(p), Value::U32(c)) => c.wrapping_sub(*p) as f64,
+        (Value::U64(p), Value::U64(c)) => c.wrapping_sub(*p) as f64,
+        (Value::I8(p), Value::I8(c)) => c.wrapping_sub(*p) as u8 as f64,
+        (Value::I16(p), Value::I16(c)) => c.wrapping_sub(*p) as u16 as f64,
+        (Value::I32(p), Value::I32(c)) => c.wrapping_sub(*p) as u32 as f64,
+        (Value::I64(p), Value::I64(c)) => c.wrapping_sub(*p) as u64 as f64,
+        (Value::F32(p), Value::F32(c)) if c >= p => (c - p) as f64,
+        (Value::F64(p), Value::F64(c)) if c >= p => c - p,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::device_adapter::Unit;
+
+    fn telemetry(time: f64, gpu_energy: Unit, global_activity: Unit, vram_read: Unit) -> Telemetry {
+        let none = TelemetryItem(None);
+        Telemetry {
+            time_stamp: TelemetryItem(Some(Unit::TimeSeconds(Value::F64(time)))),
+            gpu_energy_counter: TelemetryItem(Some(gpu_energy)),
+            gpu_voltage: none,
+            gpu_current_clock_frequency: none,
+            gpu_current_temperature: none,
+            global_activity_counter: TelemetryItem(Some(global_activity)),
+            render_compute_activity_counter: none,
+            media_activity_counter: none,
+            vram_energy_counter: none,
+            vram_voltage: none,
+            vram_current_clock_frequency: none,
+            vram_current_effective_frequency: none,
+            vram_read_bandwidth_counter: TelemetryItem(Some(vram_read)),
+            vram_write_bandwidth_counter: none,
+            vram_current_temperature: none,
+            total_card_energy_counter: none,
+            fan_speed: [none; 5],
+        }
+    }
+
+    fn item(unit: Unit) -> TelemetryItem {
+        TelemetryItem(Some(unit))
+    }
+
+    #[test]
+    fn rates() {
+        let previous = telemetry(
+            10.0,
+            Unit::EnergyJoules(Value::F64(100.0)),
+            Unit::TimeSeconds(Value::F64(1.0)),
+            Unit::MemoryBytes(Value::U64(0)),
+        );
+        let current = telemetry(
+            12.0,
+            Unit::EnergyJoules(Value::F64(130.0)),
+            Unit::TimeSeconds(Value::F64(2.5)),
+            Unit::MemoryBytes(Value::U64(4_000_000)),
+        );
+
+        let rates = TelemetryRates::between(&previous, &current).unwrap();
+        assert_eq!(rates.interval, 2.0);
+        assert_eq!(rates.gpu_power, Some(15.0));
+        assert_eq!(rates.global_activity, Some(75.0));
+        assert_eq!(rates.vram_read_bandwidth, Some(2.0));
+        assert_eq!(rates.vram_power, None);
+        assert_eq!(rates.card_power, None);
+    }
+
+    #[test]
+    fn timestamp_must_advance() {
+        let sample = |time| {
+            telemetry(
+                time,
+                Unit::EnergyJoules(Value::F64(0.0)),
+                Unit::TimeSeconds(Value::F64(0.0)),
+                Unit::MemoryBytes(Value::U64(0)),
+            )
+        };
+
+        assert_eq!(TelemetryRates::between(&sample(5.0), &sample(5.0)), None);
+        assert_eq!(TelemetryRates::between(&sample(5.0), &sample(4.0)), None);
+
+        let mut unsupported = sample(6.0);
+        unsupported.time_stamp = TelemetryItem(None);
+        assert_eq!(TelemetryRates::between(&sample(5.0), &unsupported), None);
+    }
+
+    #[test]
+    fn integer_counters_wrap() {
+        assert_eq!(
+            counter_delta(
+                &item(Unit::MemoryBytes(Value::U32(u32::MAX - 9))),
+                &item(Unit::MemoryBytes(Value::U32(10))),
+                UnitKind::MemoryBytes,
+            ),
+            Some(20.0)
+        );
+        assert_eq!(
+            counter_delta(
+                &item(Unit::MemoryBytes(Value::U8(250))),
+                &item(Unit::MemoryBytes(Value::U8(4))),
+                UnitKind::MemoryBytes,
+            ),
+            Some(10.0)
+        );
+        assert_eq!(
+            counter_delta(
+                &item(Unit::MemoryBytes(Value::I32(i32::MAX))),
+                &item(Unit::MemoryBytes(Value::I32(i32::MIN))),
+                UnitKind::MemoryBytes,
+            ),
+            Some(1.0)
+        );
+        assert_eq!(
+            counter_delta(
+                &item(Unit::MemoryBytes(Value::U64(u64::MAX))),
+                &item(Unit::MemoryBytes(Value::U64(0))),
+                UnitKind::MemoryBytes,
+            ),
+            Some(1.0)
+        );
+    }
+
+    #[test]
+    fn float_counters_running_backwards_are_rejected() {
+        assert_eq!(
+            counter_delta(
+                &item(Unit::EnergyJoules(Value::F64(10.0))),
+                &item(Unit::EnergyJoules(Value::F64(5.0))),
+                UnitKind::EnergyJoules,
+            ),
+            None
+        );
+        assert_eq!(
+            counter_delta(
+                &item(Unit::EnergyJoules(Value::F32(1.0))),
+                &item(Unit::EnergyJoules(Value::F32(1.5))),
+                UnitKind::EnergyJoules,
+            ),
+            Some(0.5)
+        );
+    }
+
+    #[test]
+    fn mismatched_counters_are_rejected() {
+        // Different units
+        assert_eq!(
+            counter_delta(
+                &item(Unit::EnergyJoules(Value::F64(1.0))),
+                &item(Unit::TimeSeconds(Value::F64(2.0))),
+                UnitKind::EnergyJoules,
+            ),
+            None
+        );
+        // Unit other than the expected one
+        assert_eq!(
+            counter_delta(
+                &item(Unit::PowerWatts(Value::F64(1.0))),
+                &item(Unit::PowerWatts(Value::F64(2.0))),
+                UnitKind::EnergyJoules,
+            ),
+            None
+        );
+        // Different data types
+        assert_eq!(
+            counter_delta(
+                &item(Unit::EnergyJoules(Value::U32(1))),
+                &item(Unit::EnergyJoules(Value::U64(2))),
+                UnitKind::EnergyJoules,
+            ),
+            None
+        );
+        // Unsupported
+        assert_eq!(
+            counter_delta(
+                &TelemetryItem(None),
+                &item(Unit::EnergyJoules(Value::F64(2.0))),
+                UnitKind::EnergyJoules,
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn sampler() {
+        let sample = |time, joules| {
+            telemetry(
+                time,
+                Unit::EnergyJoules(Value::F64(joules)),
+                Unit::TimeSeconds(Value::F64(0.0)),
+                Unit::MemoryBytes(Value::U64(0)),
+            )
+        };
+
+        let mut sampler = TelemetrySampler::new();
+        assert_eq!(sampler.push(sample(1.0, 0.0)), None);
+        assert_eq!(sampler.push(sample(2.0, 5.0)).unwrap().gpu_power, Some(5.0));
+
+        // A sample without rates still replaces the previous one
+        assert_eq!(sampler.push(sample(2.0, 5.0)), None);
+        assert_eq!(sampler.push(sample(4.0, 9.0)).unwrap().gpu_power, Some(2.0));
+
+        sampler.reset();
+        assert!(sampler.previous().is_none());
+        assert_eq!(sampler.push(sample(5.0, 10.0)), None);
+    }
+}